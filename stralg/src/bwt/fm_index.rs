@@ -0,0 +1,301 @@
+use super::{bwt, c_table};
+use crate::suffix_array::sais;
+use crate::utils::{AsStrSlice, CharacterTrait, Str};
+
+/// The default number of rows between stored occurrence counts.
+const DEFAULT_OCC_SAMPLE_RATE: usize = 64;
+
+/// The default distance between sampled suffix array positions in the text.
+const DEFAULT_SA_SAMPLE_RATE: usize = 32;
+
+/// A bit vector with constant time rank queries.
+struct RankBitVector {
+    /// The bits, 64 to a word
+    words: Vec<u64>,
+    /// The number of set bits before each word
+    counts: Vec<usize>,
+}
+
+impl RankBitVector {
+    fn new(bits: &[bool]) -> RankBitVector {
+        let mut words = vec![0u64; bits.len().div_ceil(64)];
+        for (i, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+            words[i / 64] |= 1 << (i % 64);
+        }
+        let mut counts = Vec::with_capacity(words.len());
+        let mut sum = 0;
+        for word in &words {
+            counts.push(sum);
+            sum += word.count_ones() as usize;
+        }
+        RankBitVector { words, counts }
+    }
+
+    fn get(&self, i: usize) -> bool {
+        (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    /// The number of set bits before index `i`.
+    fn rank(&self, i: usize) -> usize {
+        let mask = (1u64 << (i % 64)) - 1;
+        self.counts[i / 64] + (self.words[i / 64] & mask).count_ones() as usize
+    }
+}
+
+/// An FM-index over a string, for counting and locating occurrences of patterns
+/// without keeping the string or its full suffix array around.
+///
+/// The index consists of the Burrows-Wheeler transform of the string, the C table,
+/// an occurrence table with the rank of each character at every `occ_sample_rate`
+/// rows, and the suffix array positions that are a multiple of `sa_sample_rate`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::bwt::FMIndex;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let index = FMIndex::new(&x);
+///
+/// assert_eq!(index.count("ssi"), 2);
+/// let mut matches: Vec<usize> = index.locate("ssi").collect();
+/// matches.sort();
+/// assert_eq!(matches, vec![2, 5]);
+/// ```
+pub struct FMIndex<Char: CharacterTrait> {
+    /// The Burrows-Wheeler transform of the string
+    b: Str<Char>,
+    /// The number of characters in the string (including the sentinel) smaller than each character
+    c: Vec<usize>,
+    /// The number of rows between stored occurrence counts
+    occ_sample_rate: usize,
+    /// The occurrence counts, with `sigma` counts for every `occ_sample_rate` rows
+    occ: Vec<usize>,
+    /// Which rows have their suffix array position sampled
+    sampled: RankBitVector,
+    /// The sampled suffix array positions, in row order
+    samples: Vec<usize>,
+}
+
+impl<Char: CharacterTrait> FMIndex<Char> {
+    /// Builds the FM-index for the string `x` with the default sample rates.
+    ///
+    /// The construction runs in O(n) time, where `n` is the length of `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to build the index over.
+    ///
+    /// # Returns
+    ///
+    /// A new `FMIndex` instance.
+    pub fn new<S: AsStrSlice<Char>>(x: &S) -> FMIndex<Char> {
+        Self::with_sample_rates(x, DEFAULT_OCC_SAMPLE_RATE, DEFAULT_SA_SAMPLE_RATE)
+    }
+
+    /// Builds the FM-index for the string `x` with the given sample rates.
+    ///
+    /// Higher sample rates give a smaller index but slower queries: counting takes
+    /// O(m * occ_sample_rate) time and locating an occurrence O(sa_sample_rate *
+    /// occ_sample_rate) time, for a pattern of length `m`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to build the index over.
+    /// * `occ_sample_rate` - The number of rows between stored occurrence counts.
+    /// * `sa_sample_rate` - The distance between sampled suffix positions in `x`.
+    ///
+    /// # Returns
+    ///
+    /// A new `FMIndex` instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either sample rate is zero.
+    pub fn with_sample_rates<S: AsStrSlice<Char>>(
+        x: &S,
+        occ_sample_rate: usize,
+        sa_sample_rate: usize,
+    ) -> FMIndex<Char> {
+        assert!(occ_sample_rate > 0 && sa_sample_rate > 0);
+
+        let sa = sais(x);
+        let b = bwt(x, &sa);
+        let c = c_table(&b);
+
+        let sigma = c.len();
+        let mut occ = Vec::with_capacity((b.len() / occ_sample_rate + 1) * sigma);
+        let mut counts = vec![0; sigma];
+        for (i, &a) in b.iter().enumerate() {
+            if i.is_multiple_of(occ_sample_rate) {
+                occ.extend_from_slice(&counts);
+            }
+            counts[a.to_usize()] += 1;
+        }
+        if b.len().is_multiple_of(occ_sample_rate) {
+            occ.extend_from_slice(&counts);
+        }
+
+        let sampled_rows: Vec<bool> = sa
+            .iter()
+            .map(|&i| i.is_multiple_of(sa_sample_rate))
+            .collect();
+        let samples = sa
+            .iter()
+            .copied()
+            .filter(|&i| i.is_multiple_of(sa_sample_rate))
+            .collect();
+
+        FMIndex {
+            b,
+            c,
+            occ_sample_rate,
+            occ,
+            sampled: RankBitVector::new(&sampled_rows),
+            samples,
+        }
+    }
+
+    /// The number of occurrences of the character `a` in the rows before `i`.
+    fn rank(&self, a: usize, i: usize) -> usize {
+        let block = i / self.occ_sample_rate;
+        let start = block * self.occ_sample_rate;
+        let sampled = self.occ[block * self.c.len() + a];
+        sampled
+            + self.b[start..i]
+                .iter()
+                .filter(|c| c.to_usize() == a)
+                .count()
+    }
+
+    /// Maps a row to the row of the suffix that starts one character earlier.
+    fn lf(&self, i: usize) -> usize {
+        let a = self.b[i].to_usize();
+        self.c[a] + self.rank(a, i)
+    }
+
+    /// Returns the interval of rows whose suffixes start with `p`, using backward search.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for. It must use the same alphabet as the string.
+    ///
+    /// # Returns
+    ///
+    /// The range of rows (indices into the suffix array) whose suffixes start with `p`.
+    pub fn interval<S: AsStrSlice<Char>>(&self, p: &S) -> std::ops::Range<usize> {
+        let p = p.as_str_slice();
+        let (mut lo, mut hi) = (0, self.b.len());
+        for &a in p.iter().rev() {
+            let a = a.to_usize();
+            lo = self.c[a] + self.rank(a, lo);
+            hi = self.c[a] + self.rank(a, hi);
+            if lo >= hi {
+                return 0..0;
+            }
+        }
+        lo..hi
+    }
+
+    /// Maps `p` to the index's alphabet, or returns `None` if it has characters
+    /// that are not in the alphabet (and thus cannot occur).
+    fn map_pattern(&self, p: &str) -> Option<Str<Char>> {
+        if p.is_empty() {
+            return None;
+        }
+        self.b.translate_to_this_alphabet(p).ok()
+    }
+
+    /// Returns the number of occurrences of the pattern `p` in the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// The number of occurrences of `p`.
+    pub fn count(&self, p: &str) -> usize {
+        match self.map_pattern(p) {
+            Some(p) => self.interval(&p).len(),
+            None => 0,
+        }
+    }
+
+    /// Returns the position in the string of the suffix in row `i`.
+    fn suffix_position(&self, mut i: usize) -> usize {
+        let mut steps = 0;
+        while !self.sampled.get(i) {
+            i = self.lf(i);
+            steps += 1;
+        }
+        self.samples[self.sampled.rank(i)] + steps
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// `p` in the string.
+    ///
+    /// The occurrences are reported in the order their suffixes have in the suffix
+    /// array, not in the order they have in the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern `p`.
+    pub fn locate(&self, p: &str) -> Box<dyn Iterator<Item = usize> + Send + '_> {
+        match self.map_pattern(p) {
+            Some(p) => Box::new(self.interval(&p).map(|i| self.suffix_position(i))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{naive, Alphabet};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_rank_bit_vector() {
+        let bits: Vec<bool> = (0..200).map(|i| i % 3 == 0).collect();
+        let v = RankBitVector::new(&bits);
+        for i in 0..200 {
+            assert_eq!(v.get(i), bits[i]);
+            assert_eq!(v.rank(i), bits[..i].iter().filter(|&&b| b).count());
+        }
+    }
+
+    #[test]
+    fn test_fm_index_matches_naive() {
+        let x = "aabaabaaabaabbaabaaabbbabaabaaab";
+        let alphabet = Arc::new(Alphabet::from_str(x));
+        let s: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+        for (occ_rate, sa_rate) in [(1, 1), (3, 5), (64, 32)] {
+            let index = FMIndex::with_sample_rates(&s, occ_rate, sa_rate);
+            for p in ["a", "b", "aab", "baa", "abba", "bbb", "bbbb", x] {
+                let expected: Vec<usize> = naive(x, p).collect();
+                let mut result: Vec<usize> = index.locate(p).collect();
+                result.sort();
+                assert_eq!(index.count(p), expected.len(), "p = {p}");
+                assert_eq!(result, expected, "p = {p}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fm_index_not_in_alphabet() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let x: Str<u8> = Str::from_str("abcabc", &alphabet).unwrap();
+        let index = FMIndex::new(&x);
+        assert_eq!(index.count("abx"), 0);
+        assert_eq!(index.count(""), 0);
+        assert_eq!(index.locate("abx").count(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+pub mod fm_index;
+
+pub use fm_index::FMIndex;
+
+use crate::utils::{AsStrSlice, CharacterTrait, Str};
+
+/// Computes the C table for the string `b`.
+///
+/// The value at index `a` is the number of characters in `b` that are smaller than `a`,
+/// i.e., the index in the suffix array where the suffixes starting with `a` begin.
+/// The table has an entry for each character in the alphabet plus the sentinel.
+pub(crate) fn c_table<Char, S>(b: &S) -> Vec<usize>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let b = b.as_str_slice();
+    let mut counts = vec![0; b.alphabet.len() + 1];
+    for &a in b.iter() {
+        counts[a.to_usize()] += 1;
+    }
+    let mut c = Vec::with_capacity(counts.len());
+    let mut sum = 0;
+    for count in counts {
+        c.push(sum);
+        sum += count;
+    }
+    c
+}
+
+/// Computes the Burrows-Wheeler transform of the string `x` from its suffix array `sa`.
+///
+/// The transform is the string of characters that precede each suffix of `x$` in
+/// suffix array order, where `$` is the sentinel (zero), so it has length `n + 1`,
+/// where `n` is the length of `x`, and contains the sentinel exactly once. The suffix
+/// array must be the suffix array of `x` followed by the sentinel, as computed by
+/// [`sais`](crate::suffix_array::sais).
+///
+/// The algorithm runs in O(n) time.
+///
+/// # Arguments
+///
+/// * `x` - The string to transform.
+/// * `sa` - The suffix array of `x`.
+///
+/// # Returns
+///
+/// The Burrows-Wheeler transform of `x`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::bwt::bwt;
+/// use stralg::suffix_array::sais;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("banana"));
+/// let x: Str<u8> = Str::from_str("banana", &alphabet).unwrap();
+/// let b = bwt(&x, &sais(&x));
+/// // a -> 1, b -> 2, n -> 3, and the sentinel is zero
+/// assert_eq!(b[..], [1, 3, 3, 2, 0, 1, 1]);
+/// ```
+pub fn bwt<Char, S>(x: &S, sa: &[usize]) -> Str<Char>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let x = x.as_str_slice();
+    let sentinel = Char::try_from(0).unwrap();
+    let b = sa
+        .iter()
+        .map(|&i| if i == 0 { sentinel } else { x[i - 1] })
+        .collect();
+    Str::new(b, x.alphabet)
+}
+
+/// Reconstructs the string `x` from its Burrows-Wheeler transform `b`.
+///
+/// The transform must contain the sentinel exactly once, as computed by [`bwt`]. We
+/// reconstruct `x` from the right, using the LF-mapping to move from the row of a
+/// suffix to the row of the suffix that starts one character earlier.
+///
+/// The algorithm runs in O(n + σ) time and space, where `n` is the length of `x`
+/// and σ is the size of its alphabet.
+///
+/// # Arguments
+///
+/// * `b` - The Burrows-Wheeler transform.
+///
+/// # Returns
+///
+/// The string `x` without the sentinel.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::bwt::{bwt, inverse_bwt};
+/// use stralg::suffix_array::sais;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("banana"));
+/// let x: Str<u8> = Str::from_str("banana", &alphabet).unwrap();
+/// let b = bwt(&x, &sais(&x));
+/// assert_eq!(inverse_bwt(&b), x);
+/// ```
+pub fn inverse_bwt<Char, S>(b: &S) -> Str<Char>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let b = b.as_str_slice();
+    if b.is_empty() {
+        return Str::new(vec![], b.alphabet);
+    }
+
+    // The LF-mapping for each row: the number of smaller characters in b plus the
+    // number of occurrences of the same character before the row.
+    let mut next = c_table(&b);
+    let mut lf = vec![0; b.len()];
+    for (i, &a) in b.iter().enumerate() {
+        lf[i] = next[a.to_usize()];
+        next[a.to_usize()] += 1;
+    }
+
+    // Row zero is the sentinel suffix, so it is preceded by the last character of x.
+    let n = b.len() - 1;
+    let mut x = Vec::with_capacity(n);
+    let mut i = 0;
+    for _ in 0..n {
+        x.push(b[i]);
+        i = lf[i];
+    }
+    x.reverse();
+    Str::new(x, b.alphabet)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::suffix_array::sais;
+    use crate::Alphabet;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bwt_mississippi() {
+        let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+        let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+        let b = bwt(&x, &sais(&x));
+        // The BWT of mississippi$ is ipssm$pissii
+        let expected = Str::<u8>::from_str("ipssm", &alphabet).unwrap();
+        assert_eq!(b[..5], expected[..]);
+        assert_eq!(b[5], 0);
+        let expected = Str::<u8>::from_str("pissii", &alphabet).unwrap();
+        assert_eq!(b[6..], expected[..]);
+    }
+
+    #[test]
+    fn test_inverse_bwt() {
+        for x in [
+            "",
+            "a",
+            "aaaa",
+            "abracadabra",
+            "mississippi",
+            "gtccgatgtcgcaagtcc",
+        ] {
+            let alphabet = Arc::new(Alphabet::from_str(x));
+            let x: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+            let b = bwt(&x, &sais(&x));
+            assert_eq!(inverse_bwt(&b), x);
+        }
+    }
+
+    #[test]
+    fn test_c_table() {
+        let alphabet = Arc::new(Alphabet::from_str("banana"));
+        let x: Str<u8> = Str::from_str("banana", &alphabet).unwrap();
+        let b = bwt(&x, &sais(&x));
+        // $ -> 0, a -> 1, b -> 4, n -> 5
+        assert_eq!(c_table(&b), vec![0, 1, 4, 5]);
+    }
+}
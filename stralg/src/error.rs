@@ -0,0 +1,120 @@
+use std::fmt;
+
+/// The errors reported by the library.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::utils::{Alphabet, Str};
+/// use stralg::Error;
+///
+/// let alphabet = Arc::new(Alphabet::from_str("acgt"));
+/// match Str::<u8>::from_str("acgnt", &alphabet) {
+///     Err(Error::CharacterNotInAlphabet { ch, position }) => {
+///         assert_eq!(ch, 'n');
+///         assert_eq!(position, 3);
+///     }
+///     _ => panic!("Expected CharacterNotInAlphabet"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The character `ch`, at character index `position` in the string being
+    /// mapped, is not in the alphabet.
+    CharacterNotInAlphabet { ch: char, position: usize },
+    /// The alphabet has `size` characters, but the character type only has room
+    /// for `max` (plus the sentinel).
+    AlphabetTooLarge { size: usize, max: usize },
+    /// The character class that starts at character index `position` in a
+    /// pattern has no closing bracket.
+    UnterminatedCharacterClass { position: usize },
+    /// The character class that starts at character index `position` in a
+    /// pattern has no characters.
+    EmptyCharacterClass { position: usize },
+    /// The escape character at character index `position` is the last character
+    /// of a pattern, so there is nothing to escape.
+    DanglingEscape { position: usize },
+    /// The pattern with index `index` has length `length`, but all patterns must
+    /// have length `expected`.
+    PatternLengthMismatch {
+        index: usize,
+        length: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CharacterNotInAlphabet { ch, position } => {
+                write!(
+                    f,
+                    "character {ch:?} at position {position} is not in the alphabet"
+                )
+            }
+            Error::AlphabetTooLarge { size, max } => {
+                write!(
+                    f,
+                    "alphabet of size {size} is too large, the maximum is {max}"
+                )
+            }
+            Error::UnterminatedCharacterClass { position } => {
+                write!(f, "unterminated character class at position {position}")
+            }
+            Error::EmptyCharacterClass { position } => {
+                write!(f, "empty character class at position {position}")
+            }
+            Error::DanglingEscape { position } => {
+                write!(
+                    f,
+                    "escape character at position {position} ends the pattern"
+                )
+            }
+            Error::PatternLengthMismatch {
+                index,
+                length,
+                expected,
+            } => write!(
+                f,
+                "pattern {index} has length {length}, but all patterns must have length {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let e = Error::CharacterNotInAlphabet {
+            ch: 'x',
+            position: 2,
+        };
+        assert_eq!(
+            e.to_string(),
+            "character 'x' at position 2 is not in the alphabet"
+        );
+        let e = Error::AlphabetTooLarge {
+            size: 300,
+            max: 254,
+        };
+        assert_eq!(
+            e.to_string(),
+            "alphabet of size 300 is too large, the maximum is 254"
+        );
+    }
+
+    #[test]
+    fn test_is_std_error() {
+        let e: Box<dyn std::error::Error> = Box::new(Error::DanglingEscape { position: 0 });
+        assert_eq!(
+            e.to_string(),
+            "escape character at position 0 ends the pattern"
+        );
+    }
+}
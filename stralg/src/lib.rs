@@ -1,10 +1,19 @@
+pub mod bwt;
+pub mod error;
 pub mod patterns;
 pub mod search;
+pub mod suffix_array;
+pub mod suffix_tree;
 pub mod utils;
 
+pub use error::Error;
 pub use patterns::border_array;
 pub use patterns::strict_border_array;
+pub use patterns::z_array;
+pub use search::aho_corasick;
+pub use search::bm;
 pub use search::bmh;
 pub use search::kmp;
 pub use search::naive;
+pub use search::z_search;
 pub use utils::Alphabet;
@@ -1,4 +1,4 @@
-use crate::utils::{CharacterTrait, Str};
+use crate::utils::{AsStrSlice, CharacterTrait};
 
 /// Computes the border array for the given pattern `p`.
 ///
@@ -19,36 +19,42 @@ use crate::utils::{CharacterTrait, Str};
 /// # Examples
 ///
 /// ```
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use stralg::{border_array, utils::{Alphabet, Str}};
 ///
-/// let alphabet = Rc::new(Alphabet::from_str("abracadabra"));
+/// let alphabet = Arc::new(Alphabet::from_str("abracadabra"));
 /// let pattern: Str<u8> = Str::from_str("abracadabra", &alphabet).unwrap();
 /// let borders = border_array(&pattern);
 /// assert_eq!(borders, vec![0, 0, 0, 1, 0, 1, 0, 1, 2, 3, 4]);
 /// ```
 ///
 /// ```
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use stralg::{border_array, utils::{Alphabet, Str}};
 ///
-/// let alphabet = Rc::new(Alphabet::from_str("a"));
+/// let alphabet = Arc::new(Alphabet::from_str("a"));
 /// let pattern: Str<u8> = Str::from_str("aaaa", &alphabet).unwrap();
 /// let borders = border_array(&pattern);
 /// assert_eq!(borders, vec![0, 1, 2, 3]);
 /// ```
-pub fn border_array<Char>(p: &Str<Char>) -> Vec<usize>
+pub fn border_array<Char, S>(p: &S) -> Vec<usize>
 where
     Char: CharacterTrait,
+    S: AsStrSlice<Char>,
 {
+    border_array_of(p.as_str_slice().as_slice())
+}
+
+/// Computes the border array of the characters in `p`, see [`border_array`].
+pub(crate) fn border_array_of<Char: CharacterTrait>(p: &[Char]) -> Vec<usize> {
     let m = p.len();
     let mut ba = vec![0; m];
     let mut j = 0;
     for i in 1..m {
-        while j > 0 && &p[i] != &p[j] {
+        while j > 0 && p[i] != p[j] {
             j = ba[j - 1];
         }
-        if &p[i] == &p[j] {
+        if p[i] == p[j] {
             j += 1;
         }
         ba[i] = j;
@@ -76,10 +82,10 @@ where
 /// # Examples
 ///
 /// ```
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use stralg::{strict_border_array, utils::{Alphabet, Str}};
 ///
-/// let alphabet = Rc::new(Alphabet::from_str("abracadabra"));
+/// let alphabet = Arc::new(Alphabet::from_str("abracadabra"));
 /// let pattern: Str<u8> = Str::from_str("abracadabra", &alphabet).unwrap();
 ///
 /// let borders = strict_border_array(&pattern);
@@ -87,10 +93,10 @@ where
 /// ```
 ///
 /// ```
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use stralg::{strict_border_array, utils::{Alphabet, Str}};
 ///
-/// let alphabet = Rc::new(Alphabet::from_str("a"));
+/// let alphabet = Arc::new(Alphabet::from_str("a"));
 /// let pattern: Str<u8> = Str::from_str("aaaa", &alphabet).unwrap();
 ///
 /// let borders = strict_border_array(&pattern);
@@ -98,20 +104,26 @@ where
 /// ```
 ///
 /// ```
-/// use std::rc::Rc;
+/// use std::sync::Arc;
 /// use stralg::{strict_border_array, utils::{Alphabet, Str}};
 ///
-/// let alphabet = Rc::new(Alphabet::from_str("abcd"));
+/// let alphabet = Arc::new(Alphabet::from_str("abcd"));
 /// let pattern: Str<u8> = Str::from_str("abcd", &alphabet).unwrap();
 ///
 /// let borders = strict_border_array(&pattern);
 /// assert_eq!(borders, vec![0, 0, 0, 0]);
 /// ```
-pub fn strict_border_array<Char>(p: &Str<Char>) -> Vec<usize>
+pub fn strict_border_array<Char, S>(p: &S) -> Vec<usize>
 where
     Char: CharacterTrait,
+    S: AsStrSlice<Char>,
 {
-    let mut ba = border_array(p);
+    strict_border_array_of(p.as_str_slice().as_slice())
+}
+
+/// Computes the strict border array of the characters in `p`, see [`strict_border_array`].
+pub(crate) fn strict_border_array_of<Char: CharacterTrait>(p: &[Char]) -> Vec<usize> {
+    let mut ba = border_array_of(p);
     for j in 1..(ba.len() - 1) {
         let b = ba[j];
         if b > 0 && p[b] == p[j + 1] {
@@ -121,17 +133,91 @@ where
     ba
 }
 
+/// Computes the Z-array for the given string `x`.
+///
+/// The Z-array is an array where the value at each index `i` is the length of
+/// the longest common prefix of `x` and the suffix `x[i..]`. By convention, the
+/// value at index zero is the length of `x`.
+///
+/// The algorithm runs in O(n) time, where `n` is the length of the string.
+///
+/// # Arguments
+///
+/// * `x` - The string for which to compute the Z-array.
+///
+/// # Returns
+///
+/// A vector containing the Z-array of the string `x`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::{z_array, utils::{Alphabet, Str}};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("abracadabra"));
+/// let x: Str<u8> = Str::from_str("abracadabra", &alphabet).unwrap();
+/// let z = z_array(&x);
+/// assert_eq!(z, vec![11, 0, 0, 1, 0, 1, 0, 4, 0, 0, 1]);
+/// ```
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::{z_array, utils::{Alphabet, Str}};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("a"));
+/// let x: Str<u8> = Str::from_str("aaaa", &alphabet).unwrap();
+/// let z = z_array(&x);
+/// assert_eq!(z, vec![4, 3, 2, 1]);
+/// ```
+pub fn z_array<Char, S>(x: &S) -> Vec<usize>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    z_array_of(x.as_str_slice().as_slice())
+}
+
+/// Computes the Z-array of the characters in `x`, see [`z_array`].
+pub(crate) fn z_array_of<Char: CharacterTrait>(x: &[Char]) -> Vec<usize> {
+    let n = x.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+
+    // [l, r) is the right-most interval we have seen that matches a prefix of x
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = 0;
+        if i < r {
+            // x[i..r] matches x[i-l..r-l], so we know at least this much
+            k = z[i - l].min(r - i);
+        }
+        while i + k < n && x[k] == x[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            (l, r) = (i, i + k);
+        }
+    }
+    z
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::utils::Str;
     use crate::Alphabet;
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use super::*;
 
     #[test]
     fn test_border_array() {
         let p = "abracadabra";
-        let alpha = Rc::new(Alphabet::from_str(p));
+        let alpha = Arc::new(Alphabet::from_str(p));
         let p: Str<u8> = Str::from_str(p, &alpha).unwrap();
         let b = border_array(&p);
         assert_eq!(b, vec![0, 0, 0, 1, 0, 1, 0, 1, 2, 3, 4]);
@@ -140,7 +226,7 @@ mod tests {
     #[test]
     fn test_border_array_all_as() {
         let p = "aaaa";
-        let alpha = Rc::new(Alphabet::from_str(p));
+        let alpha = Arc::new(Alphabet::from_str(p));
         let p: Str<u8> = Str::from_str(p, &alpha).unwrap();
         let b = border_array(&p);
         assert_eq!(b, vec![0, 1, 2, 3]);
@@ -149,7 +235,7 @@ mod tests {
     #[test]
     fn test_strict_border_array() {
         let p = "abracadabra";
-        let alpha = Rc::new(Alphabet::from_str(p));
+        let alpha = Arc::new(Alphabet::from_str(p));
         let p: Str<u8> = Str::from_str(p, &alpha).unwrap();
         let b = strict_border_array(&p);
         assert_eq!(b, vec![0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 4]);
@@ -158,9 +244,37 @@ mod tests {
     #[test]
     fn test_strict_border_array_all_as() {
         let p = "aaaa";
-        let alpha = Rc::new(Alphabet::from_str(p));
+        let alpha = Arc::new(Alphabet::from_str(p));
         let p: Str<u8> = Str::from_str(p, &alpha).unwrap();
         let b = strict_border_array(&p);
         assert_eq!(b, vec![0, 0, 0, 3]);
     }
+
+    #[test]
+    fn test_z_array() {
+        let x = "abracadabra";
+        let alpha = Arc::new(Alphabet::from_str(x));
+        let x: Str<u8> = Str::from_str(x, &alpha).unwrap();
+        let z = z_array(&x);
+        assert_eq!(z, vec![11, 0, 0, 1, 0, 1, 0, 4, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_z_array_matches_definition() {
+        let x = "aabaabaaabaab";
+        let alpha = Arc::new(Alphabet::from_str(x));
+        let x: Str<u8> = Str::from_str(x, &alpha).unwrap();
+        let z = z_array(&x);
+        for i in 0..x.len() {
+            let lcp = (0..(x.len() - i)).take_while(|&k| x[k] == x[i + k]).count();
+            assert_eq!(z[i], lcp);
+        }
+    }
+
+    #[test]
+    fn test_z_array_empty() {
+        let alpha = Arc::new(Alphabet::from_str(""));
+        let x: Str<u8> = Str::from_str("", &alpha).unwrap();
+        assert_eq!(z_array(&x), vec![]);
+    }
 }
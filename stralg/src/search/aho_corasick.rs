@@ -0,0 +1,369 @@
+use super::preprocessed::{map_pattern, Algorithm, Mapped};
+use crate::utils::{CharTable, CharacterTrait, Str, StrMapper, StrMappers};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Marks a missing edge in the goto table.
+const NO_NODE: usize = usize::MAX;
+
+/// The root of the trie is always node zero.
+const ROOT: usize = 0;
+
+/// Aho-Corasick multi-pattern search, see [`AhoCorasick`].
+struct AC;
+
+/// An Aho-Corasick automaton over a set of patterns.
+struct ACAutomaton {
+    /// The trie edges, with a row per node
+    goto: CharTable<usize>,
+    /// The failure link for each node
+    failure: Vec<usize>,
+    /// The nearest node on the failure path (excluding the node itself) where a pattern ends
+    output: Vec<Option<usize>>,
+    /// The indices of the patterns that end in each node
+    ids: Vec<Vec<usize>>,
+    /// The lengths of the patterns, indexed by pattern index
+    lengths: Vec<usize>,
+}
+
+impl ACAutomaton {
+    fn new<Char: CharacterTrait>(patterns: &[&str], mapper: &StrMapper<Char>) -> ACAutomaton {
+        let mut goto = CharTable::new(mapper.alphabet.len() + 1);
+        goto.add_row(NO_NODE); // The root
+        let mut automaton = ACAutomaton {
+            goto,
+            failure: vec![ROOT],
+            output: vec![None],
+            ids: vec![vec![]],
+            lengths: Vec::with_capacity(patterns.len()),
+        };
+        for (id, p) in patterns.iter().enumerate() {
+            automaton.insert(id, &map_pattern(mapper, p)[..]);
+        }
+        automaton.build_links();
+        automaton
+    }
+
+    fn edge<Char: CharacterTrait>(&self, v: usize, a: Char) -> usize {
+        self.goto.get(v, a.to_usize())
+    }
+
+    /// Adds the pattern `p` with index `id` to the trie.
+    fn insert<Char: CharacterTrait>(&mut self, id: usize, p: &[Char]) {
+        self.lengths.push(p.len());
+        if p.is_empty() {
+            // We never report empty patterns, just as the single-pattern searches.
+            return;
+        }
+        let mut v = ROOT;
+        for &a in p.iter() {
+            let w = self.edge(v, a);
+            v = if w != NO_NODE {
+                w
+            } else {
+                let w = self.goto.add_row(NO_NODE);
+                self.failure.push(ROOT);
+                self.output.push(None);
+                self.ids.push(vec![]);
+                self.goto.set(v, a.to_usize(), w);
+                w
+            };
+        }
+        self.ids[v].push(id);
+    }
+
+    /// Sets the failure and output links in a breadth-first traversal of the trie,
+    /// so the links of a node's parent are in place when we handle the node.
+    fn build_links(&mut self) {
+        // Children of the root fail to the root
+        let mut queue: VecDeque<usize> = self.goto.entries(ROOT, NO_NODE).map(|(_, w)| w).collect();
+        while let Some(v) = queue.pop_front() {
+            for (a, w) in self.goto.entries(v, NO_NODE) {
+                let mut f = self.failure[v];
+                while f != ROOT && self.goto.get(f, a) == NO_NODE {
+                    f = self.failure[f];
+                }
+                let fw = self.goto.get(f, a);
+                let f = if fw != NO_NODE { fw } else { ROOT };
+                self.failure[w] = f;
+                self.output[w] = if self.ids[f].is_empty() {
+                    self.output[f]
+                } else {
+                    Some(f)
+                };
+                queue.push_back(w);
+            }
+        }
+    }
+
+    /// Moves from node `v` on character `a`, following failure links as needed.
+    fn step<Char: CharacterTrait>(&self, mut v: usize, a: Char) -> usize {
+        loop {
+            let w = self.edge(v, a);
+            if w != NO_NODE {
+                return w;
+            }
+            if v == ROOT {
+                return ROOT;
+            }
+            v = self.failure[v];
+        }
+    }
+}
+
+struct ACIter<Char: CharacterTrait> {
+    /// The string we are searching in
+    x: Str<Char>,
+    /// The automaton for the patterns we are searching for
+    automaton: Arc<ACAutomaton>,
+    /// The next index in the string
+    i: usize,
+    /// The current node in the automaton
+    state: usize,
+    /// The node whose patterns we are currently reporting, if any
+    out_node: Option<usize>,
+    /// The next pattern to report in `out_node`
+    out_index: usize,
+}
+
+impl<Char: CharacterTrait> Iterator for ACIter<Char> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let ACIter {
+            x,
+            automaton,
+            i,
+            state,
+            out_node,
+            out_index,
+        } = self;
+        loop {
+            // Report the patterns ending at the current position before we move on
+            if let Some(v) = *out_node {
+                if let Some(&id) = automaton.ids[v].get(*out_index) {
+                    *out_index += 1;
+                    return Some((id, *i - automaton.lengths[id]));
+                }
+                *out_node = automaton.output[v];
+                *out_index = 0;
+                continue;
+            }
+
+            if *i >= x.len() {
+                return None;
+            }
+            *state = automaton.step(*state, x[*i]);
+            *i += 1;
+            *out_node = if automaton.ids[*state].is_empty() {
+                automaton.output[*state]
+            } else {
+                Some(*state)
+            };
+        }
+    }
+}
+
+impl Algorithm for AC {
+    type Input<'a> = &'a [&'a str];
+    type Item = (usize, usize);
+    type Pattern<Char: CharacterTrait> = Arc<ACAutomaton>;
+
+    fn preprocess<Char: CharacterTrait>(
+        patterns: &[&str],
+        mapper: &StrMapper<Char>,
+    ) -> Arc<ACAutomaton> {
+        Arc::new(ACAutomaton::new(patterns, mapper))
+    }
+
+    fn search<Char: CharacterTrait>(
+        automaton: &Arc<ACAutomaton>,
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = (usize, usize)> + Send> {
+        Box::new(ACIter {
+            x,
+            automaton: automaton.clone(),
+            i: 0,
+            state: ROOT,
+            out_node: None,
+            out_index: 0,
+        })
+    }
+}
+
+/// A set of patterns preprocessed for the Aho-Corasick multi-pattern matching algorithm.
+///
+/// The automaton is built once, when the `AhoCorasick` is created, and can then be
+/// reused for searching any number of texts.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::AhoCorasick;
+///
+/// let search = AhoCorasick::new(&["he", "she", "his", "hers"]);
+/// let matches: Vec<(usize, usize)> = search.search("ushers").collect();
+/// assert_eq!(matches, vec![(1, 1), (0, 2), (3, 2)]);
+/// ```
+pub struct AhoCorasick {
+    automaton: Mapped<AC>,
+}
+
+impl AhoCorasick {
+    /// Builds the Aho-Corasick automaton for the patterns `patterns`.
+    ///
+    /// The construction runs in O(mσ) time, where `m` is the total length of the
+    /// patterns and σ is the size of their combined alphabet. For large alphabets
+    /// the trie only stores the edges it has, so it takes O(m) space.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The patterns to search for.
+    ///
+    /// # Returns
+    ///
+    /// A new `AhoCorasick` instance.
+    pub fn new(patterns: &[&str]) -> AhoCorasick {
+        let mapper = StrMappers::new_from_strs(patterns);
+        let automaton = Mapped::new(mapper, patterns);
+        AhoCorasick { automaton }
+    }
+
+    /// Returns an iterator over the occurrences of the patterns in the text `x`.
+    ///
+    /// Each occurrence is reported as a pair of the pattern's index in the slice
+    /// the automaton was built from and the starting index of the occurrence in `x`.
+    /// Occurrences are reported in order of their end positions, and occurrences
+    /// that end at the same position are reported from the longest to the shortest.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the patterns.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over `(pattern_id, position)` pairs.
+    pub fn search(&self, x: &str) -> Box<dyn Iterator<Item = (usize, usize)> + Send> {
+        self.automaton.search(x)
+    }
+}
+
+/// Returns an iterator over the occurrences of the patterns `patterns` in the
+/// text `x` using the Aho-Corasick algorithm.
+///
+/// The Aho-Corasick algorithm builds a trie of the patterns, with failure links
+/// that, like the border array in KMP, tell us where to continue when we cannot
+/// extend a match, and output links that find all the patterns that end at a
+/// given position in the text.
+///
+/// The algorithm runs in O(n + m + z) time, where `n` is the length of the text,
+/// `m` is the total length of the patterns, and `z` is the number of occurrences.
+///
+/// If you search for the same patterns in several texts, use [`AhoCorasick`] to
+/// only build the automaton once.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the patterns.
+/// * `patterns` - The patterns to search for.
+///
+/// # Returns
+///
+/// An iterator over `(pattern_id, position)` pairs, where `pattern_id` is the
+/// index of the pattern in `patterns` and `position` is the starting index of
+/// the occurrence in `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::aho_corasick;
+///
+/// let matches: Vec<(usize, usize)> = aho_corasick("abracadabra", &["abr", "a", "cad"]).collect();
+/// assert_eq!(
+///     matches,
+///     vec![(1, 0), (0, 0), (1, 3), (1, 5), (2, 4), (1, 7), (0, 7), (1, 10)]
+/// );
+/// ```
+pub fn aho_corasick(x: &str, patterns: &[&str]) -> Box<dyn Iterator<Item = (usize, usize)> + Send> {
+    if x.is_empty() || patterns.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    AhoCorasick::new(patterns).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    fn sorted(mut v: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let result: Vec<(usize, usize)> =
+            aho_corasick("ushers", &["he", "she", "his", "hers"]).collect();
+        assert_eq!(result, vec![(1, 1), (0, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn test_aho_corasick_empty() {
+        let result: Vec<(usize, usize)> = aho_corasick("", &["a"]).collect();
+        assert_eq!(result, vec![]);
+        let result: Vec<(usize, usize)> = aho_corasick("abc", &[]).collect();
+        assert_eq!(result, vec![]);
+        let result: Vec<(usize, usize)> = aho_corasick("abc", &["", "b"]).collect();
+        assert_eq!(result, vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_aho_corasick_duplicate_patterns() {
+        let result: Vec<(usize, usize)> = aho_corasick("aaa", &["aa", "aa"]).collect();
+        assert_eq!(result, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_aho_corasick_characters_not_in_patterns() {
+        let result: Vec<(usize, usize)> = aho_corasick("xabxxab", &["ab", "b"]).collect();
+        assert_eq!(result, vec![(0, 1), (1, 2), (0, 5), (1, 6)]);
+    }
+
+    #[test]
+    fn test_aho_corasick_matches_naive() {
+        let x = "abracadabra abracadabra cadabra dab";
+        let patterns = ["a", "ab", "abra", "bra", "cad", "dab", "rac", "xyz", "ra c"];
+        let search = AhoCorasick::new(&patterns);
+        let result = sorted(search.search(x).collect());
+        let mut expected = vec![];
+        for (id, p) in patterns.iter().enumerate() {
+            expected.extend(naive(x, p).map(|i| (id, i)));
+        }
+        assert_eq!(result, sorted(expected));
+    }
+
+    #[test]
+    fn test_aho_corasick_large_alphabet() {
+        // Too many characters for a dense goto table
+        let x: String = (0..1000)
+            .map(|i| std::char::from_u32(0x4e00 + i).unwrap())
+            .collect();
+        let patterns: Vec<String> = (0..10)
+            .map(|i| x.chars().skip(97 * i).take(3 + i).collect())
+            .collect();
+        let patterns: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+        let result: Vec<(usize, usize)> = aho_corasick(&x, &patterns).collect();
+        let expected: Vec<(usize, usize)> = (0..10).map(|i| (i, 97 * i)).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_aho_corasick_reused() {
+        let search = AhoCorasick::new(&["ab", "ba"]);
+        let result: Vec<(usize, usize)> = search.search("aba").collect();
+        assert_eq!(result, vec![(0, 0), (1, 1)]);
+        let result: Vec<(usize, usize)> = search.search("bab").collect();
+        assert_eq!(result, vec![(1, 0), (0, 1)]);
+    }
+}
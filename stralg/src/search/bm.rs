@@ -0,0 +1,303 @@
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::patterns::border_array_of;
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
+
+/// The Boyer-Moore (BM) string matching algorithm, see [`bm`].
+pub struct BM;
+
+/// A pattern preprocessed for Boyer-Moore search.
+pub struct BMPattern<Char>
+where
+    Char: CharacterTrait,
+{
+    /// The pattern we are searching for
+    p: Vec<Char>,
+    /// One plus the right-most position of each character in the pattern (zero if it doesn't occur)
+    bad_char_table: Vec<usize>,
+    /// The strong good suffix shift for a mismatch at each position in the pattern
+    good_suffix_table: Vec<usize>,
+    /// The smallest period of the pattern, used for shifting after a match
+    period: usize,
+}
+
+/// Builds the bad character table for the pattern `p`.
+///
+/// The table holds, for each character in the alphabet, one plus the right-most
+/// position where it occurs in `p`, or zero if it does not occur. With a mismatch
+/// against character `a` at position `j`, the bad character rule lets us shift
+/// the pattern `j + 1 - table[a]` positions (if this is positive).
+fn build_bad_char_table<Char>(p: &[Char], sigma: usize) -> Vec<usize>
+where
+    Char: CharacterTrait,
+{
+    let mut bad_char_table = vec![0; sigma];
+    for i in 0..p.len() {
+        bad_char_table[p[i].to_usize()] = i + 1;
+    }
+    bad_char_table
+}
+
+/// Computes, for each position `i` in `p`, the length of the longest common
+/// suffix of `p[..=i]` and `p`.
+///
+/// The algorithm runs in O(m) time, where `m` is the length of the pattern.
+fn suffix_lengths<Char>(p: &[Char]) -> Vec<usize>
+where
+    Char: CharacterTrait,
+{
+    // We work with signed indices here since the window [g, f] can move past the
+    // beginning of the pattern.
+    let m = p.len() as isize;
+    let mut suff = vec![0; p.len()];
+    suff[p.len() - 1] = p.len();
+    let mut f = m - 1;
+    let mut g = m - 1;
+    for i in (0..(m - 1)).rev() {
+        let known = suff[(i + m - 1 - f) as usize];
+        if i > g && (known as isize) < i - g {
+            suff[i as usize] = known;
+        } else {
+            g = g.min(i);
+            f = i;
+            while g >= 0 && p[g as usize] == p[(g + m - 1 - f) as usize] {
+                g -= 1;
+            }
+            suff[i as usize] = (f - g) as usize;
+        }
+    }
+    suff
+}
+
+/// Builds the strong good suffix table for the pattern `p`.
+///
+/// If we have a mismatch at position `j`, then `p[j+1..]` matched the text, and
+/// the table holds the smallest shift that aligns another occurrence of this suffix,
+/// preceded by a character different from `p[j]`, or, if there isn't one, the
+/// longest prefix of `p` that is a suffix of `p[j+1..]`.
+///
+/// The algorithm runs in O(m) time, where `m` is the length of the pattern.
+fn build_good_suffix_table<Char>(p: &[Char]) -> Vec<usize>
+where
+    Char: CharacterTrait,
+{
+    let m = p.len();
+    let suff = suffix_lengths(p);
+    let mut gs = vec![m; m];
+
+    // Case 2: a prefix of p matches a suffix of the matched part of the text.
+    // Prefixes that are also suffixes are exactly those with suff[i] == i + 1,
+    // and we want the longest such for each j, so we run through them from the right.
+    let mut j = 0;
+    for i in (0..m).rev() {
+        if suff[i] == i + 1 {
+            while j < m - 1 - i {
+                if gs[j] == m {
+                    gs[j] = m - 1 - i;
+                }
+                j += 1;
+            }
+        }
+    }
+
+    // Case 1: the matched suffix occurs again in p, preceded by a different character.
+    // Later (right-most) occurrences give smaller shifts and overwrite earlier ones.
+    for i in 0..(m - 1) {
+        gs[m - 1 - suff[i]] = m - 1 - i;
+    }
+
+    gs
+}
+
+struct BMIter<Char, X>
+where
+    Char: CharacterTrait,
+{
+    x: X,
+    pattern: Arc<BMPattern<Char>>,
+    /// The current position of the pattern in the text
+    i: usize,
+    /// The number of characters at the start of the pattern that we know
+    /// match the text at the current position (Galil's rule)
+    known: usize,
+}
+
+impl<Char, X> BMIter<Char, X>
+where
+    Char: CharacterTrait,
+{
+    fn new(x: X, pattern: Arc<BMPattern<Char>>) -> BMIter<Char, X> {
+        BMIter {
+            x,
+            pattern,
+            i: 0,
+            known: 0,
+        }
+    }
+}
+
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for BMIter<Char, X> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let BMIter {
+            x,
+            pattern,
+            i,
+            known,
+        } = self;
+        let x = x.as_ref();
+        let BMPattern {
+            p,
+            bad_char_table,
+            good_suffix_table,
+            period,
+        } = pattern.as_ref();
+        let n = x.len();
+        let m = p.len();
+        while *i + m <= n {
+            // Compare from the right, but never further left than the part we
+            // already know matches.
+            let mut j = m;
+            while j > *known && p[j - 1] == x[*i + j - 1] {
+                j -= 1;
+            }
+            if j == *known {
+                // We have a match. Shifting by the period leaves a prefix of
+                // length m - period that we know matches the text.
+                let hit = *i;
+                *i += period;
+                *known = m - period;
+                return Some(hit);
+            }
+
+            // Mismatch at position j - 1
+            let j = j - 1;
+            let bad_char_shift = (j + 1).saturating_sub(bad_char_table[x[*i + j].to_usize()]);
+            *i += good_suffix_table[j].max(bad_char_shift);
+            *known = 0;
+        }
+        None
+    }
+}
+
+impl ExactAlgorithm for BM {
+    type Pattern<Char: CharacterTrait> = BMPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> BMPattern<Char> {
+        BMPattern {
+            p: p.to_vec(),
+            bad_char_table: build_bad_char_table(p, sigma),
+            good_suffix_table: build_good_suffix_table(p),
+            period: p.len() - border_array_of(p)[p.len() - 1],
+        }
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<BMPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(BMIter::new(x, pattern))
+    }
+}
+
+/// A pattern preprocessed for the Boyer-Moore (BM) string matching algorithm.
+///
+/// The alphabet, the bad character table and the strong good suffix table of the
+/// pattern are computed once, when the `BMSearch` is created, and can then be reused
+/// for searching any number of texts. The preprocessing runs in O(m + σ) time, where
+/// `m` is the length of the pattern and σ is the size of its alphabet.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{BMSearch, Searcher};
+///
+/// let search = BMSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type BMSearch = Preprocessed<BM>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Boyer-Moore (BM) string matching algorithm.
+///
+/// The BM algorithm compares the pattern against the text from right to left, and
+/// on a mismatch shifts the pattern by the largest of the shifts given by the bad
+/// character rule and the strong good suffix rule. After a match, it shifts by the
+/// period of the pattern and uses Galil's rule to avoid comparing the part of the
+/// pattern it already knows matches.
+///
+/// The algorithm runs in worst time O(n + m), where `n` is the length of the text
+/// and `m` is the length of the pattern, and is sublinear on average.
+///
+/// If you search for the same pattern in several texts, use [`BMSearch`] to
+/// only preprocess the pattern once.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::bm;
+///
+/// let text = "abracadabra";
+/// let pattern = "abr";
+/// let matches: Vec<usize> = bm(text, pattern).collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn bm(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    BMSearch::new(p).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{utils::Str, Alphabet};
+
+    #[test]
+    fn test_bad_charater_table() {
+        let p = "abracadabra";
+        let alphabet = Arc::new(Alphabet::from_str(p)); // $abcdr
+        let p: Str<u8> = Str::from_str(p, &alphabet).unwrap();
+        // One plus the right-most index, or zero if the character doesn't occur
+        // $ -> 0, a -> 11, b -> 9, c -> 5, d -> 7, r -> 10
+        let result = build_bad_char_table(&p[..], alphabet.len() + 1);
+        assert_eq!(result, vec![0, 11, 9, 5, 7, 10]);
+    }
+
+    #[test]
+    fn test_suffix_lengths() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let p: Str<u8> = Str::from_str("abcab", &alphabet).unwrap();
+        assert_eq!(suffix_lengths(&p[..]), vec![0, 2, 0, 0, 5]);
+    }
+
+    #[test]
+    fn test_good_suffix_table() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let p: Str<u8> = Str::from_str("abcab", &alphabet).unwrap();
+        assert_eq!(build_good_suffix_table(&p[..]), vec![3, 3, 3, 5, 1]);
+
+        let alphabet = Arc::new(Alphabet::from_str("a"));
+        let p: Str<u8> = Str::from_str("aaaa", &alphabet).unwrap();
+        assert_eq!(build_good_suffix_table(&p[..]), vec![1, 2, 3, 4]);
+    }
+}
@@ -1,51 +1,57 @@
-use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
 
-struct BMHSearch<Char>
+/// The Boyer-Moore-Horspool (BMH) string matching algorithm, see [`bmh`].
+pub struct BMH;
+
+/// A pattern preprocessed for BMH search.
+pub struct BMHPattern<Char>
 where
     Char: CharacterTrait,
 {
-    x: Str<Char>,
-    p: Str<Char>,
-    i: usize,
+    /// The pattern we are searching for
+    p: Vec<Char>,
+    /// The jump table indexed by the last character in the current window
     bad_char_table: Vec<usize>,
 }
 
-fn build_bad_char_table<Char>(p: &Str<Char>) -> Vec<usize>
+fn build_bad_char_table<Char>(p: &[Char], sigma: usize) -> Vec<usize>
 where
     Char: CharacterTrait,
 {
-    let mut bad_char_table = vec![p.len(); p.alphabet.len() + 1];
+    let mut bad_char_table = vec![p.len(); sigma];
     for i in 0..(p.len() - 1) {
         bad_char_table[p[i].to_usize()] = p.len() - i - 1;
     }
     bad_char_table
 }
 
-impl<Char> BMHSearch<Char>
+struct BMHIter<Char, X>
 where
     Char: CharacterTrait,
 {
-    fn new(x: Str<Char>, p: Str<Char>) -> BMHSearch<Char> {
-        let bad_char_table = build_bad_char_table(&p);
-        BMHSearch {
-            x,
-            p,
-            i: 0,
-            bad_char_table,
-        }
+    x: X,
+    pattern: Arc<BMHPattern<Char>>,
+    i: usize,
+}
+
+impl<Char, X> BMHIter<Char, X>
+where
+    Char: CharacterTrait,
+{
+    fn new(x: X, pattern: Arc<BMHPattern<Char>>) -> BMHIter<Char, X> {
+        BMHIter { x, pattern, i: 0 }
     }
 }
 
-impl<Char: CharacterTrait> Iterator for BMHSearch<Char> {
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for BMHIter<Char, X> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        let BMHSearch {
-            x,
-            p,
-            i,
-            bad_char_table,
-        } = self;
+        let BMHIter { x, pattern, i } = self;
+        let x = x.as_ref();
+        let BMHPattern { p, bad_char_table } = pattern.as_ref();
         let n = x.len();
         let m = p.len();
         while *i <= n - m {
@@ -64,47 +70,102 @@ impl<Char: CharacterTrait> Iterator for BMHSearch<Char> {
     }
 }
 
-fn bmh_impl<Char>(x: &str, p: &str, mapper: StrMapper<Char>) -> Box<dyn Iterator<Item = usize>>
-where
-    Char: CharacterTrait,
-{
-    let x = mapper.map_str(x).unwrap(); // We built the string from x so this cannot fail...
-    let p = match mapper.map_str(p) {
-        Ok(p) => p,
-        Err(_) => return Box::new(std::iter::empty()),
-    };
-    Box::new(BMHSearch::new(x, p))
-}
+impl ExactAlgorithm for BMH {
+    type Pattern<Char: CharacterTrait> = BMHPattern<Char>;
 
-pub fn bmh(x: &str, p: &str) -> Box<dyn Iterator<Item = usize>> {
-    if x.is_empty() || p.is_empty() {
-        return Box::new(std::iter::empty());
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> BMHPattern<Char> {
+        BMHPattern {
+            p: p.to_vec(),
+            bad_char_table: build_bad_char_table(p, sigma),
+        }
     }
-    if x.len() < p.len() {
-        return Box::new(std::iter::empty());
+
+    fn search<'a, Char, X>(
+        pattern: Arc<BMHPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(BMHIter::new(x, pattern))
     }
+}
+
+/// A pattern preprocessed for the Boyer-Moore-Horspool (BMH) string matching algorithm.
+///
+/// The alphabet and the bad character table of the pattern are computed once, when
+/// the `BMHSearch` is created, and can then be reused for searching any number of texts.
+/// The preprocessing runs in O(m + σ) time, where `m` is the length of the pattern
+/// and σ is the size of its alphabet.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{BMHSearch, Searcher};
+///
+/// let search = BMHSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type BMHSearch = Preprocessed<BMH>;
 
-    let mapper = StrMappers::new_from_str(x).unwrap(); // We unwrap because we don't expect alphabet larger than u16
-    match mapper {
-        StrMappers::U8Mapper(mapper) => bmh_impl(x, p, mapper),
-        StrMappers::U16Mapper(mapper) => bmh_impl(x, p, mapper),
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Boyer-Moore-Horspool (BMH) string matching algorithm.
+///
+/// The BMH algorithm compares the pattern against the text from right to left, and
+/// after each comparison shifts the pattern based on the last character in the
+/// current window, so it aligns with the right-most occurrence of that character in
+/// the pattern.
+///
+/// The algorithm runs in worst time O(n * m) but is sublinear on average.
+///
+/// If you search for the same pattern in several texts, use [`BMHSearch`] to
+/// only preprocess the pattern once.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::bmh;
+///
+/// let text = "abracadabra";
+/// let pattern = "abr";
+/// let matches: Vec<usize> = bmh(text, pattern).collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn bmh(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
     }
+    BMHSearch::new(p).search(x)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{utils::Str, Alphabet};
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     #[test]
     fn test_bad_charater_table() {
         let p = "abracadabra"; // len = 11
-        let alphabet = Rc::new(Alphabet::from_str(p)); // $abcdr
+        let alphabet = Arc::new(Alphabet::from_str(p)); // $abcdr
         let p: Str<u8> = Str::from_str(p, &alphabet).unwrap();
         // Jumps table (right-most index from the right, or full pattern if unknown)
         // $ -> 11, a -> 3, b -> 2, c -> 6, d -> 4, r -> 1
-        let result = build_bad_char_table(&p);
+        let result = build_bad_char_table(&p[..], alphabet.len() + 1);
         assert_eq!(result, vec![11, 3, 2, 6, 4, 1]);
     }
 }
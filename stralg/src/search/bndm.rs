@@ -0,0 +1,174 @@
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
+
+/// The number of pattern positions that fit in the bit masks.
+const WORD_SIZE: usize = u64::BITS as usize;
+
+/// The Backward Nondeterministic DAWG Matching (BNDM) algorithm, see [`bndm`].
+pub struct BNDM;
+
+/// A pattern preprocessed for BNDM search.
+pub struct BNDMPattern<Char: CharacterTrait> {
+    /// The pattern we are searching for
+    p: Vec<Char>,
+    /// The length of the window we scan with the bit masks, at most a word
+    w: usize,
+    /// For each character `a`, bit `w - 1 - i` is set if `p[i] == a`, for `i < w`
+    masks: Vec<u64>,
+}
+
+struct BNDMIter<Char: CharacterTrait, X> {
+    /// The string we are searching in
+    x: X,
+    /// The preprocessed pattern we are searching for
+    pattern: Arc<BNDMPattern<Char>>,
+    /// The current position of the window in the text
+    pos: usize,
+}
+
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for BNDMIter<Char, X> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let BNDMIter { x, pattern, pos } = self;
+        let x = x.as_ref();
+        let BNDMPattern { p, w, masks } = pattern.as_ref();
+        let (m, w) = (p.len(), *w);
+        let first = 1u64 << (w - 1);
+
+        while *pos + m <= x.len() {
+            // Scan the window backwards. Bit `w - 1 - i` in `d` is set if the
+            // suffix of the window we have read occurs at position `i` in the
+            // first `w` characters of the pattern.
+            let mut d = if w == WORD_SIZE { !0 } else { (1 << w) - 1 };
+            let mut j = w;
+            let mut last = w;
+            let mut found = false;
+            while d != 0 {
+                d &= masks[x[*pos + j - 1].to_usize()];
+                j -= 1;
+                if d & first != 0 {
+                    if j > 0 {
+                        // A prefix of the pattern matches here, so this is the
+                        // next window that can hold an occurrence.
+                        last = j;
+                    } else {
+                        // The first `w` characters match, check the rest
+                        found = x[*pos + w..*pos + m] == p[w..];
+                        break;
+                    }
+                }
+                d <<= 1;
+            }
+            let j = *pos;
+            *pos += last;
+            if found {
+                return Some(j);
+            }
+        }
+        None
+    }
+}
+
+impl ExactAlgorithm for BNDM {
+    type Pattern<Char: CharacterTrait> = BNDMPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> BNDMPattern<Char> {
+        let w = p.len().min(WORD_SIZE);
+        let mut masks = vec![0u64; sigma];
+        for (i, a) in p[..w].iter().enumerate() {
+            masks[a.to_usize()] |= 1 << (w - 1 - i);
+        }
+        BNDMPattern {
+            p: p.to_vec(),
+            w,
+            masks,
+        }
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<BNDMPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(BNDMIter { x, pattern, pos: 0 })
+    }
+}
+
+/// A pattern preprocessed for the Backward Nondeterministic DAWG Matching (BNDM)
+/// algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{BNDMSearch, Searcher};
+///
+/// let search = BNDMSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type BNDMSearch = Preprocessed<BNDM>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Backward Nondeterministic DAWG Matching (BNDM)
+/// algorithm.
+///
+/// The algorithm reads each window of the text from right to left, simulating the
+/// nondeterministic suffix automaton of the reversed pattern with bit-parallelism,
+/// until the text read is no longer a factor of the pattern. The shift is to the
+/// last position where the text read was a prefix of the pattern. Patterns longer
+/// than 64 characters are scanned with their first 64 characters and the rest is
+/// verified.
+///
+/// The algorithm runs in O(nm) worst-case time and O(n log_σ(m) / m) average time
+/// for patterns up to 64 characters, where `n` is the length of the text and `m`
+/// is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::bndm;
+///
+/// let matches: Vec<usize> = bndm("acgtacgtaacgt", "acgt").collect();
+/// assert_eq!(matches, vec![0, 4, 9]);
+/// ```
+pub fn bndm(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    BNDMSearch::new(p).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    #[test]
+    fn test_long_patterns() {
+        let x: String = "acgtacgtaacgtt".repeat(20);
+        for (i, m) in [(0, 64), (3, 65), (5, 100), (1, 200)] {
+            let p = &x[i..i + m];
+            let expected: Vec<usize> = naive(&x, p).collect();
+            let result: Vec<usize> = bndm(&x, p).collect();
+            assert!(!expected.is_empty());
+            assert_eq!(result, expected, "m = {m}");
+        }
+    }
+}
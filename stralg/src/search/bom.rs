@@ -0,0 +1,193 @@
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::{CharTable, CharacterTrait};
+use std::sync::Arc;
+
+/// Marks a missing transition in the factor oracle.
+const NO_STATE: usize = usize::MAX;
+
+/// The Backward Oracle Matching (BOM) algorithm, see [`bom`].
+pub struct BOM;
+
+/// A pattern preprocessed for Backward Oracle Matching.
+pub struct BOMPattern<Char: CharacterTrait> {
+    /// The length of the pattern
+    m: usize,
+    /// The transitions of the factor oracle of the reversed pattern, with a row per state
+    delta: CharTable<usize>,
+    /// The characters are only used for indexing the transition table
+    chars: std::marker::PhantomData<Char>,
+}
+
+impl<Char: CharacterTrait> BOMPattern<Char> {
+    fn new(p: &[Char], sigma: usize) -> BOMPattern<Char> {
+        let m = p.len();
+        let mut delta = CharTable::new(sigma);
+        for _ in 0..=m {
+            delta.add_row(NO_STATE);
+        }
+        // The supply function, linking each state to the state reached by the
+        // longest repeated suffix of the string that leads to it.
+        let mut supply = vec![NO_STATE; m + 1];
+        for i in 1..=m {
+            let a = p[m - i].to_usize(); // We build the oracle over the reversed pattern
+            delta.set(i - 1, a, i);
+            let mut k = supply[i - 1];
+            while k != NO_STATE && delta.get(k, a) == NO_STATE {
+                delta.set(k, a, i);
+                k = supply[k];
+            }
+            supply[i] = if k == NO_STATE { 0 } else { delta.get(k, a) };
+        }
+        BOMPattern {
+            m,
+            delta,
+            chars: std::marker::PhantomData,
+        }
+    }
+}
+
+struct BOMIter<Char: CharacterTrait, X> {
+    /// The string we are searching in
+    x: X,
+    /// The preprocessed pattern we are searching for
+    pattern: Arc<BOMPattern<Char>>,
+    /// The current position of the window in the text
+    pos: usize,
+}
+
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for BOMIter<Char, X> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let BOMIter { x, pattern, pos } = self;
+        let x = x.as_ref();
+        let BOMPattern { m, delta, .. } = pattern.as_ref();
+        let m = *m;
+
+        while *pos + m <= x.len() {
+            // Read the window backwards until the oracle has no transition, which
+            // means that what we have read is not a factor of the pattern.
+            let mut state = 0;
+            let mut j = m;
+            while j > 0 {
+                state = delta.get(state, x[*pos + j - 1].to_usize());
+                if state == NO_STATE {
+                    break;
+                }
+                j -= 1;
+            }
+            let i = *pos;
+            if j == 0 {
+                // The only string of length m the oracle recognises is the pattern
+                *pos += 1;
+                return Some(i);
+            }
+            // No occurrence can start before the character we failed on
+            *pos += j;
+        }
+        None
+    }
+}
+
+impl ExactAlgorithm for BOM {
+    type Pattern<Char: CharacterTrait> = BOMPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> BOMPattern<Char> {
+        BOMPattern::new(p, sigma)
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<BOMPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(BOMIter { x, pattern, pos: 0 })
+    }
+}
+
+/// A pattern preprocessed for the Backward Oracle Matching (BOM) algorithm.
+///
+/// The factor oracle of the reversed pattern is built once, in O(mσ) time, where
+/// `m` is the length of the pattern and σ is the size of its alphabet. For large
+/// alphabets the oracle only stores the transitions it has, at most 2m of them, so
+/// it takes O(m) space.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{BOMSearch, Searcher};
+///
+/// let search = BOMSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type BOMSearch = Preprocessed<BOM>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Backward Oracle Matching (BOM) algorithm.
+///
+/// The algorithm reads each window of the text from right to left through the
+/// factor oracle of the reversed pattern. The oracle recognises at least all the
+/// factors of the reversed pattern, so when it has no transition, what we have
+/// read is not a factor and the window can be shifted past the failing character.
+///
+/// The algorithm runs in O(nm) worst-case time and O(n log_σ(m) / m) average time,
+/// where `n` is the length of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::bom;
+///
+/// let matches: Vec<usize> = bom("acgtacgtaacgt", "acgt").collect();
+/// assert_eq!(matches, vec![0, 4, 9]);
+/// ```
+pub fn bom(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    BOMSearch::new(p).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_factor_oracle() {
+        // The ranks of "abbbaab" over the alphabet {a, b}
+        let oracle = BOMPattern::new(&[1u8, 2, 2, 2, 1, 1, 2], 3);
+        let accepts = |s: &str| {
+            s.chars().try_fold(0, |state, c| {
+                let a = (c as usize) - ('a' as usize) + 1;
+                let next = oracle.delta.get(state, a);
+                (next != NO_STATE).then_some(next)
+            })
+        };
+        // The oracle of the reversed pattern "baabbba" recognises all its factors...
+        let r = "baabbba";
+        for i in 0..r.len() {
+            for j in i..=r.len() {
+                assert!(accepts(&r[i..j]).is_some(), "{}", &r[i..j]);
+            }
+        }
+        // ...and the only string of length m it recognises is the reversed pattern.
+        assert_eq!(accepts(r), Some(r.len()));
+        assert_eq!(accepts("aaaaaaa"), None);
+    }
+}
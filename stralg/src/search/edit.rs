@@ -0,0 +1,401 @@
+use super::preprocessed::{map_pattern, Algorithm, Mapped};
+use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use std::sync::Arc;
+
+/// Approximate search reporting end positions, see [`edit`].
+struct EditEnds;
+
+/// Approximate search reporting alignments, see [`edit_alignments`].
+struct EditAlignments;
+
+/// An approximate occurrence of a pattern in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditMatch {
+    /// The index in the text where the occurrence starts
+    pub start: usize,
+    /// The index in the text one past where the occurrence ends
+    pub end: usize,
+    /// The edit distance between the pattern and `x[start..end]`
+    pub distance: usize,
+    /// The alignment of the pattern against `x[start..end]` as a CIGAR string, using
+    /// `=` for matches, `X` for mismatches, `I` for characters only in the pattern,
+    /// and `D` for characters only in the text
+    pub cigar: String,
+}
+
+/// Sellers' algorithm, computing the edit distance columns over the text one at a time.
+struct EditIter<Char: CharacterTrait> {
+    /// The text, shared with the alignments we build for the occurrences
+    x: Arc<Str<Char>>,
+    p: Arc<Str<Char>>,
+    k: usize,
+    /// The next position in the text
+    j: usize,
+    /// The current column, `column[i]` is the smallest edit distance between
+    /// `p[..i]` and a substring of the text ending at `j`
+    column: Vec<usize>,
+    /// The last row in the column with a value of at most `k` (Ukkonen's cutoff)
+    last_active: usize,
+}
+
+impl<Char: CharacterTrait> EditIter<Char> {
+    fn new(x: Arc<Str<Char>>, p: Arc<Str<Char>>, k: usize) -> EditIter<Char> {
+        let m = p.len();
+        // Before we have seen any of the text, p[..i] needs i insertions.
+        let column: Vec<usize> = (0..=m).collect();
+        let last_active = k.min(m);
+        EditIter {
+            x,
+            p,
+            k,
+            j: 0,
+            column,
+            last_active,
+        }
+    }
+
+    /// Computes the next column from `x[j]`, only down to one row past the last
+    /// active row, since rows below that have values larger than `k`.
+    fn next_column(&mut self) {
+        let EditIter {
+            x,
+            p,
+            k,
+            j,
+            column,
+            last_active,
+        } = self;
+        let m = p.len();
+        let a = x[*j];
+        let bottom = (*last_active + 1).min(m);
+
+        // Row zero stays zero since an occurrence can start anywhere.
+        let mut diagonal = column[0];
+        for i in 1..=bottom {
+            let substitution = diagonal + usize::from(p[i - 1] != a);
+            let deletion = column[i] + 1; // x[j] is not in the alignment of p
+            let insertion = column[i - 1] + 1; // p[i - 1] is not in the alignment of x
+            diagonal = column[i];
+            column[i] = substitution.min(deletion).min(insertion);
+        }
+        // Rows below the cutoff are not computed, so mark them as too large
+        for value in column.iter_mut().skip(bottom + 1) {
+            *value = *k + 1;
+        }
+
+        *last_active = bottom;
+        while *last_active > 0 && column[*last_active] > *k {
+            *last_active -= 1;
+        }
+        *j += 1;
+    }
+}
+
+impl<Char: CharacterTrait> Iterator for EditIter<Char> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        while self.j < self.x.len() {
+            self.next_column();
+            let m = self.p.len();
+            if self.last_active == m {
+                return Some((self.j, self.column[m]));
+            }
+        }
+        None
+    }
+}
+
+/// Aligns the pattern against a suffix of `x[..end]` with at most `distance` edits,
+/// and returns the start of the alignment and its CIGAR string.
+fn align<Char: CharacterTrait>(
+    x: &Str<Char>,
+    p: &Str<Char>,
+    end: usize,
+    distance: usize,
+) -> (usize, String) {
+    let m = p.len();
+    let w = end.saturating_sub(m + distance);
+    let width = end - w;
+
+    // d[i][j] is the edit distance between p[..i] and the best suffix of x[w..w + j]
+    let mut d = vec![vec![0; width + 1]; m + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=m {
+        for j in 1..=width {
+            let substitution = d[i - 1][j - 1] + usize::from(p[i - 1] != x[w + j - 1]);
+            d[i][j] = substitution.min(d[i][j - 1] + 1).min(d[i - 1][j] + 1);
+        }
+    }
+
+    // Backtrack from the end, preferring matches and mismatches over gaps.
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (m, width);
+    while i > 0 {
+        if j > 0 && d[i][j] == d[i - 1][j - 1] + usize::from(p[i - 1] != x[w + j - 1]) {
+            ops.push(if p[i - 1] == x[w + j - 1] { '=' } else { 'X' });
+            i -= 1;
+            j -= 1;
+        } else if d[i][j] == d[i - 1][j] + 1 {
+            ops.push('I');
+            i -= 1;
+        } else {
+            ops.push('D');
+            j -= 1;
+        }
+    }
+    ops.reverse();
+
+    // Run-length encode the operations
+    let mut cigar = String::new();
+    let mut k = 0;
+    while k < ops.len() {
+        let run = ops[k..].iter().take_while(|&&op| op == ops[k]).count();
+        cigar.push_str(&format!("{}{}", run, ops[k]));
+        k += run;
+    }
+    (w + j, cigar)
+}
+
+impl Algorithm for EditAlignments {
+    /// The pattern and the maximal edit distance
+    type Input<'a> = (&'a str, usize);
+    type Item = EditMatch;
+    type Pattern<Char: CharacterTrait> = (Arc<Str<Char>>, usize);
+
+    fn preprocess<Char: CharacterTrait>(
+        input: (&str, usize),
+        mapper: &StrMapper<Char>,
+    ) -> (Arc<Str<Char>>, usize) {
+        EditEnds::preprocess(input, mapper)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, k): &(Arc<Str<Char>>, usize),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = EditMatch> + Send> {
+        let (x, p) = (Arc::new(x), p.clone());
+        let iter = EditIter::new(x.clone(), p.clone(), *k);
+        Box::new(iter.map(move |(end, distance)| {
+            let (start, cigar) = align(&x, &p, end, distance);
+            EditMatch {
+                start,
+                end,
+                distance,
+                cigar,
+            }
+        }))
+    }
+}
+
+/// Returns an iterator over the approximate occurrences of the pattern `p` in the
+/// text `x` within edit distance `k`, including the start of each occurrence and
+/// its alignment against the pattern.
+///
+/// The end positions are found as in [`edit`], and for each of them we align the
+/// pattern against the text that ends there to find a start position and a CIGAR
+/// string. If several alignments are optimal, we pick the one that has the fewest
+/// gaps at the end of the pattern.
+///
+/// The search runs in O(nk) expected time, plus O(m(m + k)) time for each
+/// occurrence, where `n` is the length of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `k` - The maximal edit distance.
+///
+/// # Returns
+///
+/// An iterator over the approximate occurrences, ordered by their end positions.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{edit_alignments, EditMatch};
+///
+/// let matches: Vec<EditMatch> = edit_alignments("abracadabra", "acd", 1).collect();
+/// assert_eq!(matches[0].start, 3);
+/// assert_eq!(matches[0].end, 5);
+/// assert_eq!(matches[0].cigar, "2=1I");
+/// ```
+pub fn edit_alignments(x: &str, p: &str, k: usize) -> Box<dyn Iterator<Item = EditMatch> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+
+    let mapper = StrMappers::new_from_str(p);
+    Mapped::<EditAlignments>::new(mapper, (p, k)).search(x)
+}
+
+impl Algorithm for EditEnds {
+    /// The pattern and the maximal edit distance
+    type Input<'a> = (&'a str, usize);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = (Arc<Str<Char>>, usize);
+
+    fn preprocess<Char: CharacterTrait>(
+        (p, k): (&str, usize),
+        mapper: &StrMapper<Char>,
+    ) -> (Arc<Str<Char>>, usize) {
+        (Arc::new(map_pattern(mapper, p)), k)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, k): &(Arc<Str<Char>>, usize),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        Box::new(EditIter::new(Arc::new(x), p.clone(), *k).map(|(end, _)| end))
+    }
+}
+
+/// Returns an iterator over the end positions of approximate occurrences of the
+/// pattern `p` in the text `x` within edit distance `k`.
+///
+/// An end position `j` is reported if some substring `x[i..j]` can be turned into
+/// `p` with at most `k` substitutions, insertions and deletions. We use Sellers'
+/// algorithm, which computes the edit distance dynamic programming table one
+/// column at a time with free starting positions in the text, and Ukkonen's cutoff,
+/// which only computes each column down to the last row with a value of at most `k`.
+///
+/// The algorithm runs in O(nk) expected time and O(nm) worst time, where `n` is the
+/// length of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `k` - The maximal edit distance.
+///
+/// # Returns
+///
+/// An iterator over the end positions (one past the last character) of the
+/// approximate occurrences of `p` in `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::edit;
+///
+/// let ends: Vec<usize> = edit("abracadabra", "abr", 0).collect();
+/// assert_eq!(ends, vec![3, 10]);
+/// let ends: Vec<usize> = edit("abracadabra", "acd", 1).collect();
+/// assert_eq!(ends, vec![5, 6, 7]);
+/// ```
+pub fn edit(x: &str, p: &str, k: usize) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+
+    let mapper = StrMappers::new_from_str(p);
+    Mapped::<EditEnds>::new(mapper, (p, k)).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    /// The edit distance between two strings, computed directly.
+    fn edit_distance(a: &[char], b: &[char]) -> usize {
+        let mut d: Vec<usize> = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            let mut diagonal = d[0];
+            d[0] = i;
+            for j in 1..=b.len() {
+                let substitution = diagonal + usize::from(a[i - 1] != b[j - 1]);
+                diagonal = d[j];
+                d[j] = substitution.min(d[j] + 1).min(d[j - 1] + 1);
+            }
+        }
+        d[b.len()]
+    }
+
+    /// The end positions with an occurrence within distance k, computed directly.
+    fn naive_ends(x: &str, p: &str, k: usize) -> Vec<usize> {
+        let x: Vec<char> = x.chars().collect();
+        let p: Vec<char> = p.chars().collect();
+        (1..=x.len())
+            .filter(|&j| (0..=j).any(|i| edit_distance(&x[i..j], &p) <= k))
+            .collect()
+    }
+
+    /// Applies a CIGAR string to check that it aligns p against x[start..end].
+    fn check_cigar(x: &str, p: &str, m: &EditMatch) {
+        let x: Vec<char> = x.chars().collect();
+        let p: Vec<char> = p.chars().collect();
+        let (mut i, mut j, mut edits) = (0, m.start, 0);
+        let mut count = 0;
+        for c in m.cigar.chars() {
+            if let Some(d) = c.to_digit(10) {
+                count = count * 10 + d as usize;
+                continue;
+            }
+            for _ in 0..count {
+                match c {
+                    '=' => assert_eq!(p[i], x[j]),
+                    'X' => assert_ne!(p[i], x[j]),
+                    _ => {}
+                }
+                if c != '=' {
+                    edits += 1;
+                }
+                if c != 'D' {
+                    i += 1;
+                }
+                if c != 'I' {
+                    j += 1;
+                }
+            }
+            count = 0;
+        }
+        assert_eq!((i, j), (p.len(), m.end));
+        assert_eq!(edits, m.distance);
+    }
+
+    #[test]
+    fn test_edit_zero_is_exact() {
+        let x = "aabaabaaabaabbaabaaab";
+        for p in ["a", "aab", "abba", "bbb", "c"] {
+            let expected: Vec<usize> = naive(x, p).map(|i| i + p.len()).collect();
+            assert_eq!(edit(x, p, 0).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_edit_matches_naive() {
+        let x = "acgtacgttgcaacgtaggtcatgcatgcaacgt";
+        for p in ["acgt", "tgca", "ggggg", "acgtacgtac", "x", "axgt"] {
+            for k in 0..3 {
+                let result: Vec<usize> = edit(x, p, k).collect();
+                assert_eq!(result, naive_ends(x, p, k), "p = {p}, k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_edit_alignments() {
+        let x = "acgtacgttgcaacgtaggtcatgcatgcaacgt";
+        for p in ["acgt", "tgca", "ggggg", "acgtacgtac", "axgt"] {
+            for k in 0..3 {
+                let ends: Vec<usize> = edit(x, p, k).collect();
+                let matches: Vec<EditMatch> = edit_alignments(x, p, k).collect();
+                assert_eq!(matches.iter().map(|m| m.end).collect::<Vec<_>>(), ends);
+                for m in &matches {
+                    assert!(m.distance <= k);
+                    check_cigar(x, p, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edit_empty() {
+        assert_eq!(edit("", "a", 1).count(), 0);
+        assert_eq!(edit("a", "", 1).count(), 0);
+        assert_eq!(edit_alignments("", "a", 1).count(), 0);
+    }
+}
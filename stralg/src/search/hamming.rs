@@ -0,0 +1,282 @@
+use super::preprocessed::{map_pattern, Algorithm, Mapped};
+use crate::suffix_array::LCE;
+use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+
+/// Naive search with `k` mismatches, see [`hamming_naive`].
+struct HammingNaive;
+
+/// Search with `k` mismatches using the kangaroo method, see [`hamming`].
+struct Kangaroo;
+
+struct HammingNaiveIter<Char: CharacterTrait> {
+    x: Str<Char>,
+    p: Str<Char>,
+    k: usize,
+    i: usize,
+}
+
+impl<Char: CharacterTrait> Iterator for HammingNaiveIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let HammingNaiveIter { x, p, k, i } = self;
+        let m = p.len();
+        while *i + m <= x.len() {
+            let j = *i;
+            *i += 1;
+            let mismatches = x[j..(j + m)]
+                .iter()
+                .zip(p.iter())
+                .filter(|(a, b)| a != b)
+                .take(*k + 1)
+                .count();
+            if mismatches <= *k {
+                return Some(j);
+            }
+        }
+        None
+    }
+}
+
+impl Algorithm for HammingNaive {
+    /// The pattern and the maximal number of mismatches
+    type Input<'a> = (&'a str, usize);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = (Str<Char>, usize);
+
+    fn preprocess<Char: CharacterTrait>(
+        (p, k): (&str, usize),
+        mapper: &StrMapper<Char>,
+    ) -> (Str<Char>, usize) {
+        (map_pattern(mapper, p), k)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, k): &(Str<Char>, usize),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if x.len() < p.len() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(HammingNaiveIter {
+            x,
+            p: p.clone(),
+            k: *k,
+            i: 0,
+        })
+    }
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` with at most `k` mismatches, using the naive algorithm.
+///
+/// The naive algorithm compares `p` against the text at every position from `0`
+/// to `n - m` and counts the mismatches, stopping when there are more than `k`.
+///
+/// The algorithm runs in worst time O((n - m + 1) * m), where `n` is the length
+/// of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `k` - The maximal number of mismatches.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x` with Hamming distance at most `k`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::hamming_naive;
+///
+/// let matches: Vec<usize> = hamming_naive("abracadabra", "abc", 1).collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn hamming_naive(x: &str, p: &str, k: usize) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+
+    let mapper = StrMappers::new_from_str(p);
+    Mapped::<HammingNaive>::new(mapper, (p, k)).search(x)
+}
+
+struct KangarooIter {
+    /// Longest common extension queries over `p` followed by `x`
+    lce: LCE,
+    /// The length of the pattern
+    m: usize,
+    /// The length of the text
+    n: usize,
+    /// The maximal number of mismatches
+    k: usize,
+    /// The next position in the text
+    i: usize,
+}
+
+impl KangarooIter {
+    /// Checks if `p` matches at position `i` with at most `k` mismatches, jumping
+    /// over matching stretches with LCE queries.
+    fn matches_at(&self, i: usize) -> bool {
+        let KangarooIter { lce, m, k, .. } = self;
+        let mut j = 0;
+        let mut mismatches = 0;
+        loop {
+            // The text starts at offset m in the string we built the LCE over
+            j += lce.lce(j, m + i + j);
+            if j >= *m {
+                return true;
+            }
+            mismatches += 1;
+            if mismatches > *k {
+                return false;
+            }
+            j += 1; // Skip the mismatch
+            if j == *m {
+                return true;
+            }
+        }
+    }
+}
+
+impl Iterator for KangarooIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.i + self.m <= self.n {
+            let i = self.i;
+            self.i += 1;
+            if self.matches_at(i) {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+impl Algorithm for Kangaroo {
+    /// The pattern and the maximal number of mismatches
+    type Input<'a> = (&'a str, usize);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = (Str<Char>, usize);
+
+    fn preprocess<Char: CharacterTrait>(
+        input: (&str, usize),
+        mapper: &StrMapper<Char>,
+    ) -> (Str<Char>, usize) {
+        HammingNaive::preprocess(input, mapper)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, k): &(Str<Char>, usize),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        // We built the alphabet from both x and p, so no character in x was lost.
+        let (n, m) = (x.len(), p.len());
+        if n < m {
+            return Box::new(std::iter::empty());
+        }
+
+        // We don't need a separator between p and x, since we never look at more than
+        // m - j characters of an extension starting at offset j in the pattern.
+        let mut px: Vec<Char> = Vec::with_capacity(n + m);
+        px.extend(p.iter());
+        px.extend(x.iter());
+        let lce = LCE::new(&Str::new(px, &p.alphabet));
+        Box::new(KangarooIter {
+            lce,
+            m,
+            n,
+            k: *k,
+            i: 0,
+        })
+    }
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` with at most `k` mismatches, using the kangaroo method.
+///
+/// The kangaroo method preprocesses `p` and `x` for longest common extension
+/// (LCE) queries, and at each position in the text it jumps from one mismatch
+/// to the next with an LCE query, so it only needs `k + 1` queries per position.
+///
+/// The algorithm runs in O((n + m) log(n + m) + nk) time, where `n` is the length
+/// of the text and `m` is the length of the pattern; the first term is for
+/// building the LCE structure.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `k` - The maximal number of mismatches.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x` with Hamming distance at most `k`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::hamming;
+///
+/// let matches: Vec<usize> = hamming("abracadabra", "abc", 1).collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = hamming("abracadabra", "abc", 2).collect();
+/// assert_eq!(matches, vec![0, 2, 3, 5, 7]);
+/// ```
+pub fn hamming(x: &str, p: &str, k: usize) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+
+    let mapper = StrMappers::new_from_strs(&[x, p]);
+    Mapped::<Kangaroo>::new(mapper, (p, k)).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    #[test]
+    fn test_hamming_zero_mismatches_is_exact() {
+        let x = "aabaabaaabaabbaabaaab";
+        for p in ["a", "aab", "abba", "bbb", "c"] {
+            let expected: Vec<usize> = naive(x, p).collect();
+            assert_eq!(hamming_naive(x, p, 0).collect::<Vec<_>>(), expected);
+            assert_eq!(hamming(x, p, 0).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_hamming_matches_naive() {
+        let x = "acgtacgttgcaacgtaggtcatgcatgcaacgt";
+        for p in ["acgt", "tgca", "ggggg", "acgtacgtac", "x", "axgt"] {
+            for k in 0..4 {
+                let expected: Vec<usize> = hamming_naive(x, p, k).collect();
+                let result: Vec<usize> = hamming(x, p, k).collect();
+                assert_eq!(result, expected, "p = {p}, k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hamming_all_positions() {
+        let result: Vec<usize> = hamming("abcd", "xy", 2).collect();
+        assert_eq!(result, vec![0, 1, 2]);
+        let result: Vec<usize> = hamming_naive("abcd", "xy", 2).collect();
+        assert_eq!(result, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_hamming_empty() {
+        assert_eq!(hamming("", "a", 1).count(), 0);
+        assert_eq!(hamming("a", "", 1).count(), 0);
+        assert_eq!(hamming("a", "aa", 1).count(), 0);
+        assert_eq!(hamming_naive("a", "aa", 1).count(), 0);
+    }
+}
@@ -1,55 +1,65 @@
-use crate::strict_border_array;
-use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::patterns::strict_border_array_of;
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
 
-struct KMPSearch<Char: CharacterTrait> {
-    /// The string we are searching in
-    x: Str<Char>,
+/// The Knuth-Morris-Pratt (KMP) string matching algorithm, see [`kmp`].
+pub struct KMP;
+
+/// A pattern preprocessed for KMP search.
+pub struct KMPPattern<Char: CharacterTrait> {
     /// The pattern we are searching for
-    p: Str<Char>,
-    /// The border array of the pattern
+    p: Vec<Char>,
+    /// The strict border array of the pattern
     ba: Vec<usize>,
+}
+
+struct KMPIter<Char: CharacterTrait, X> {
+    /// The string we are searching in
+    x: X,
+    /// The preprocessed pattern we are searching for
+    pattern: Arc<KMPPattern<Char>>,
     /// The current index in the string
     x_index: usize,
     /// The current index in the pattern
     p_index: usize,
 }
 
-impl<Char: CharacterTrait> KMPSearch<Char> {
-    fn new(x: Str<Char>, p: Str<Char>, ba: Vec<usize>) -> KMPSearch<Char> {
-        KMPSearch {
+impl<Char: CharacterTrait, X> KMPIter<Char, X> {
+    fn new(x: X, pattern: Arc<KMPPattern<Char>>) -> KMPIter<Char, X> {
+        KMPIter {
             x,
-            p,
-            ba,
+            pattern,
             x_index: 0,
             p_index: 0,
         }
     }
 }
 
-impl<Char: CharacterTrait> Iterator for KMPSearch<Char> {
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for KMPIter<Char, X> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        let KMPSearch {
+        let KMPIter {
             x,
-            p,
-            ba,
+            pattern,
             x_index: i,
             p_index: j,
-            ..
         } = self;
+        let x = x.as_ref();
+        let KMPPattern { p, ba } = pattern.as_ref();
 
         let n = x.len();
         let m = p.len();
 
         while *i < n {
             // Shift pattern until it matches the border
-            while *j > 0 && &x[*i] != &p[*j] {
+            while *j > 0 && x[*i] != p[*j] {
                 *j = ba[*j - 1];
             }
 
             // Move one step forward (if we can)
-            if &x[*i] == &p[*j] {
+            if x[*i] == p[*j] {
                 *j += 1;
             }
 
@@ -66,6 +76,46 @@ impl<Char: CharacterTrait> Iterator for KMPSearch<Char> {
     }
 }
 
+impl ExactAlgorithm for KMP {
+    type Pattern<Char: CharacterTrait> = KMPPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], _sigma: usize) -> KMPPattern<Char> {
+        let ba = strict_border_array_of(p);
+        KMPPattern { p: p.to_vec(), ba }
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<KMPPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(KMPIter::new(x, pattern))
+    }
+}
+
+/// A pattern preprocessed for the Knuth-Morris-Pratt (KMP) string matching algorithm.
+///
+/// The alphabet and the strict border array of the pattern are computed once, when
+/// the `KMPSearch` is created, and can then be reused for searching any number of texts.
+/// The preprocessing runs in O(m) time, where `m` is the length of the pattern, and
+/// each search in O(n) time, where `n` is the length of the text.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{KMPSearch, Searcher};
+///
+/// let search = KMPSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type KMPSearch = Preprocessed<KMP>;
+
 /// Returns an iterator over the starting indices of occurrences of the pattern
 /// `p` in the text `x` using the Knuth-Morris-Pratt (KMP) string matching algorithm.
 ///
@@ -76,6 +126,9 @@ impl<Char: CharacterTrait> Iterator for KMPSearch<Char> {
 /// The algorithm runs in O(n + m) time, where `n` is the length of the text, and
 /// `m` is the length of the pattern.
 ///
+/// If you search for the same pattern in several texts, use [`KMPSearch`] to
+/// only preprocess the pattern once.
+///
 /// # Arguments
 ///
 /// * `x` - The text in which to search for the pattern.
@@ -96,32 +149,11 @@ impl<Char: CharacterTrait> Iterator for KMPSearch<Char> {
 /// let matches: Vec<usize> = kmp(text, pattern).collect();
 /// assert_eq!(matches, vec![0, 7]);
 /// ```
-pub fn kmp(x: &str, p: &str) -> Box<dyn Iterator<Item = usize>> {
+pub fn kmp(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
     if x.is_empty() || p.is_empty() {
         return Box::new(std::iter::empty());
     }
-    if x.len() < p.len() {
-        return Box::new(std::iter::empty());
-    }
-
-    let mapper = StrMappers::new_from_str(x).unwrap(); // We unwrap because we don't expect alphabet larger than u16
-    match mapper {
-        StrMappers::U8Mapper(mapper) => kmp_impl(x, p, mapper),
-        StrMappers::U16Mapper(mapper) => kmp_impl(x, p, mapper),
-    }
-}
-
-fn kmp_impl<Char>(x: &str, p: &str, mapper: StrMapper<Char>) -> Box<dyn Iterator<Item = usize>>
-where
-    Char: CharacterTrait,
-{
-    let x = mapper.map_str(x).unwrap(); // We built the string from x so this cannot fail...
-    let p = match mapper.map_str(p) {
-        Ok(p) => p,
-        Err(_) => return Box::new(std::iter::empty()),
-    };
-    let ba = strict_border_array(&p);
-    Box::new(KMPSearch::new(x, p, ba))
+    KMPSearch::new(p).search(x)
 }
 
 #[cfg(test)]
@@ -0,0 +1,244 @@
+use std::ops::Range;
+
+/// An occurrence of a pattern in a text, with its position both as character
+/// indices and as byte offsets into the text.
+///
+/// The search algorithms work on mapped strings, where each character is one
+/// position, so the indices they report are character indices. For non-ASCII
+/// texts those differ from the byte offsets needed for slicing the `&str`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// The character index of the start of the occurrence
+    pub start: usize,
+    /// The character index one past the end of the occurrence
+    pub end: usize,
+    /// The byte offset of the start of the occurrence
+    pub byte_start: usize,
+    /// The byte offset one past the end of the occurrence
+    pub byte_end: usize,
+}
+
+impl Match {
+    /// Returns the range of character indices of the occurrence.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the range of byte offsets of the occurrence, which can be used for
+    /// slicing the text.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_end
+    }
+}
+
+/// A position in a text, as both a character index and a byte offset.
+struct Cursor {
+    char_pos: usize,
+    byte_pos: usize,
+}
+
+impl Cursor {
+    /// Moves the cursor forward to the character index `target` and returns its byte
+    /// offset, or `None` if the text ends before `target`.
+    ///
+    /// We only scan the characters between the old and the new position, so a
+    /// sequence of increasing targets takes linear time in total.
+    fn seek(&mut self, x: &str, target: usize) -> Option<usize> {
+        debug_assert!(target >= self.char_pos, "the cursor only moves forward");
+        let mut chars = x[self.byte_pos..].chars();
+        while self.char_pos < target {
+            self.byte_pos += chars.next()?.len_utf8();
+            self.char_pos += 1;
+        }
+        Some(self.byte_pos)
+    }
+}
+
+/// An iterator that turns the starting indices of occurrences into [`Match`]es.
+pub struct MatchIter<'a, I: Iterator<Item = usize>> {
+    /// The text the occurrences are in
+    x: &'a str,
+    /// The length of the pattern in characters
+    m: usize,
+    /// The pattern, if we must check that the starts are occurrences of it
+    p: Option<&'a str>,
+    /// The starting indices of the occurrences, in increasing order
+    starts: I,
+    /// The position of the last start
+    start: Cursor,
+    /// The position of the last end
+    end: Cursor,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for MatchIter<'_, I> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            let start = self.starts.next()?;
+            let end = start + self.m;
+            // If the occurrence runs past the end of the text, so do all later ones
+            let byte_start = self.start.seek(self.x, start)?;
+            let byte_end = self.end.seek(self.x, end)?;
+            if self.p.is_some_and(|p| &self.x[byte_start..byte_end] != p) {
+                continue;
+            }
+            return Some(Match {
+                start,
+                end,
+                byte_start,
+                byte_end,
+            });
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = usize>> MatchIter<'a, I> {
+    /// Creates an iterator over the occurrences of a pattern of length `m` in `x`
+    /// that start at `starts`, which must be increasing indices of occurrences.
+    pub(crate) fn new(x: &'a str, m: usize, starts: I) -> MatchIter<'a, I> {
+        MatchIter {
+            x,
+            m,
+            p: None,
+            starts,
+            start: Cursor {
+                char_pos: 0,
+                byte_pos: 0,
+            },
+            end: Cursor {
+                char_pos: 0,
+                byte_pos: 0,
+            },
+        }
+    }
+}
+
+/// Adds the ends and the byte offsets to the starting indices of occurrences of
+/// the pattern `p` in the text `x`, as reported by the exact search algorithms.
+///
+/// The starting indices are sorted, so they can come in any order, and indices
+/// where `p` does not occur in `x` are dropped. Computing the offsets then takes
+/// O(n + k log k) time, where `n` is the length of the text and `k` the number of
+/// starting indices. If you search with a [`Preprocessed`](super::Preprocessed)
+/// pattern, [`Preprocessed::search_matches`](super::Preprocessed::search_matches)
+/// gives you the matches directly.
+///
+/// # Arguments
+///
+/// * `x` - The text the occurrences are in.
+/// * `p` - The pattern that was searched for.
+/// * `starts` - The starting indices of the occurrences, as character indices.
+///
+/// # Returns
+///
+/// An iterator over the occurrences as [`Match`]es, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{kmp, with_byte_offsets};
+///
+/// let x = "blåbærsyltetøy og blåbær";
+/// let p = "blåbær";
+/// let matches: Vec<_> = with_byte_offsets(x, p, kmp(x, p)).collect();
+/// assert_eq!(matches[1].start, 18);
+/// assert_eq!(matches[1].byte_start, 21);
+/// for m in matches {
+///     assert_eq!(&x[m.byte_range()], p);
+/// }
+/// ```
+pub fn with_byte_offsets<'a, I>(
+    x: &'a str,
+    p: &'a str,
+    starts: I,
+) -> MatchIter<'a, std::vec::IntoIter<usize>>
+where
+    I: Iterator<Item = usize>,
+{
+    let mut starts: Vec<usize> = starts.collect();
+    starts.sort_unstable();
+    starts.dedup();
+    MatchIter {
+        p: Some(p),
+        ..MatchIter::new(x, p.chars().count(), starts.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::search::{KMPSearch, Searcher};
+    use crate::{bmh, kmp, naive};
+
+    #[test]
+    fn test_ascii_offsets_are_indices() {
+        let x = "abracadabra";
+        let matches: Vec<Match> = with_byte_offsets(x, "abr", naive(x, "abr")).collect();
+        assert_eq!(
+            matches,
+            vec![
+                Match {
+                    start: 0,
+                    end: 3,
+                    byte_start: 0,
+                    byte_end: 3
+                },
+                Match {
+                    start: 7,
+                    end: 10,
+                    byte_start: 7,
+                    byte_end: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multibyte_offsets() {
+        // Characters of one, two, three and four bytes
+        let x = "aé€😀aé€😀";
+        let p = "é€😀";
+        let matches: Vec<Match> = with_byte_offsets(x, p, bmh(x, p)).collect();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].range(), 1..4);
+        assert_eq!(matches[0].byte_range(), 1..10);
+        assert_eq!(matches[1].range(), 5..8);
+        assert_eq!(matches[1].byte_range(), 11..20);
+        for m in matches {
+            assert_eq!(&x[m.byte_range()], p);
+        }
+    }
+
+    #[test]
+    fn test_decreasing_starts() {
+        // The starts are sorted, so we scan the text once
+        let x = "ææææ";
+        let matches: Vec<Match> = with_byte_offsets(x, "æ", [3, 1, 3].into_iter()).collect();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].byte_range(), 2..4);
+        assert_eq!(matches[1].byte_range(), 6..8);
+    }
+
+    #[test]
+    fn test_starts_that_are_not_occurrences() {
+        // Starts where p does not occur, and starts past the end of x, are dropped
+        let x = "aæbæ";
+        let matches: Vec<Match> = with_byte_offsets(x, "æ", [0, 1, 3, 4, 9].into_iter()).collect();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].range(), 1..2);
+        assert_eq!(matches[1].range(), 3..4);
+        assert_eq!(matches[1].byte_range(), 4..6);
+    }
+
+    #[test]
+    fn test_search_matches() {
+        let x = "blåbærsyltetøy og blåbær";
+        let p = "blåbær";
+        let search = KMPSearch::new(p);
+        let matches: Vec<Match> = search.search_matches(x).collect();
+        let expected: Vec<Match> = with_byte_offsets(x, p, kmp(x, p)).collect();
+        assert_eq!(matches, expected);
+        assert_eq!(matches.len(), 2);
+    }
+}
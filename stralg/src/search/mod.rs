@@ -1,17 +1,208 @@
+pub mod aho_corasick;
+pub mod bm;
 pub mod bmh;
+pub mod bndm;
+pub mod bom;
+pub mod edit;
+pub mod hamming;
 pub mod kmp;
+pub mod matches;
+pub mod myers;
 pub mod naive;
+pub mod preprocessed;
+pub mod rabin_karp;
+pub mod shift_and;
+pub mod two_way;
+pub mod wildcard;
+pub mod z_search;
 
-pub use bmh::bmh;
-pub use kmp::kmp;
-pub use naive::naive;
+pub use aho_corasick::{aho_corasick, AhoCorasick};
+pub use bm::{bm, BMSearch};
+pub use bmh::{bmh, BMHSearch};
+pub use bndm::{bndm, BNDMSearch};
+pub use bom::{bom, BOMSearch};
+pub use edit::{edit, edit_alignments, EditMatch};
+pub use hamming::{hamming, hamming_naive};
+pub use kmp::{kmp, KMPSearch};
+pub use matches::{with_byte_offsets, Match, MatchIter};
+pub use myers::{myers, MyersSearch};
+pub use naive::{naive, NaiveSearch};
+pub use preprocessed::{ExactAlgorithm, Preprocessed};
+pub use rabin_karp::{rabin_karp, rabin_karp_multi, RabinKarpMulti, RabinKarpSearch};
+pub use shift_and::{shift_and, shift_or, ShiftAndSearch};
+pub use two_way::{two_way, TwoWaySearch};
+pub use wildcard::{wildcard, wildcard_naive};
+pub use z_search::{z_search, ZSearch};
+
+/// A common interface for exact search algorithms.
+///
+/// A `Searcher` preprocesses a pattern once, when it is created, and can then search
+/// for the pattern in any number of texts. The trait is object safe, so the algorithm
+/// can be selected at runtime through a `Box<dyn Searcher>`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{BMHSearch, KMPSearch, NaiveSearch, Searcher};
+///
+/// fn searcher(algorithm: &str, p: &str) -> Box<dyn Searcher> {
+///     match algorithm {
+///         "kmp" => Box::new(KMPSearch::new(p)),
+///         "bmh" => Box::new(BMHSearch::new(p)),
+///         _ => Box::new(NaiveSearch::new(p)),
+///     }
+/// }
+///
+/// for algorithm in ["naive", "kmp", "bmh"] {
+///     let s = searcher(algorithm, "abr");
+///     let matches: Vec<usize> = s.search("abracadabra").collect();
+///     assert_eq!(matches, vec![0, 7]);
+/// }
+/// ```
+pub trait Searcher {
+    /// Preprocesses the pattern `p` so it can be searched for in texts.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// A new searcher for the pattern.
+    fn new(p: &str) -> Self
+    where
+        Self: Sized;
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// in the text `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern in
+    /// the text `x`, in increasing order.
+    fn search(&self, x: &str) -> Box<dyn Iterator<Item = usize> + Send>;
+}
+
+/// Exact search in raw bytes.
+///
+/// The searchers for a single pattern built on [`Preprocessed`] can also search
+/// bytes that need not be valid UTF-8. The bytes are searched as they are, without
+/// mapping them to an alphabet, so the search borrows the text instead of copying
+/// it. The other algorithms in this module only search strings.
+///
+/// A pattern given as a string is searched for as its UTF-8 bytes, and a pattern
+/// given as bytes is found in strings wherever it is the encoding of characters in
+/// the string.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{ByteSearcher, KMPSearch, Searcher};
+///
+/// let s = KMPSearch::from_bytes(b"\xffab");
+/// let matches: Vec<usize> = s.search_bytes(b"\xffab\x00\xffab").collect();
+/// assert_eq!(matches, vec![0, 4]);
+///
+/// let s = KMPSearch::new("bå");
+/// let matches: Vec<usize> = s.search_bytes("abåb".as_bytes()).collect();
+/// assert_eq!(matches, vec![1]);
+/// ```
+pub trait ByteSearcher: Searcher {
+    /// Preprocesses the pattern `p`, given as raw bytes, so it can be searched for
+    /// in bytes with [`ByteSearcher::search_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// A new searcher for the pattern.
+    fn from_bytes(p: &[u8]) -> Self
+    where
+        Self: Sized;
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// in the bytes `x`, without decoding them as UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The bytes in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the byte offsets of occurrences of the pattern in `x`,
+    /// in increasing order.
+    fn search_bytes<'a>(&self, x: &'a [u8]) -> Box<dyn Iterator<Item = usize> + Send + 'a>;
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use paste::paste;
+    use std::sync::Arc;
 
-    type SearchFn<'a> = fn(&'a str, &'a str) -> Box<dyn Iterator<Item = usize> + 'a>;
+    type SearchFn = for<'a> fn(&'a str, &'a str) -> Box<dyn Iterator<Item = usize> + Send + 'a>;
+
+    /// Runs a `Searcher` as a search function, so any algorithm implementing
+    /// the trait can be plugged into `search_tests!`.
+    fn searcher<S: Searcher>(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        S::new(p).search(x)
+    }
+
+    fn search_reused_pattern<S: Searcher>() {
+        let s = S::new("aba");
+        let result: Vec<usize> = s.search("ababa").collect();
+        assert_eq!(result, vec![0, 2]);
+        let result: Vec<usize> = s.search("xyz").collect();
+        assert_eq!(result, vec![]);
+        let result: Vec<usize> = s.search("abxaba").collect();
+        assert_eq!(result, vec![3]);
+    }
+
+    fn search_bytes<S: ByteSearcher>() {
+        // Bytes that are not valid UTF-8, including zero and bytes that do not occur
+        // in the pattern.
+        let s = S::from_bytes(&[0x00, 0xff, 0x00]);
+        let result: Vec<usize> = s
+            .search_bytes(&[0xff, 0x00, 0xff, 0x00, 0xfe, 0x00])
+            .collect();
+        assert_eq!(result, vec![1]);
+
+        let letters = [0x00, 0xc3, 0xff];
+        for m in 1..=3 {
+            for p in all_byte_strings(&letters, m) {
+                let s = S::from_bytes(&p);
+                for n in 0..=6 {
+                    for x in all_byte_strings(&letters, n) {
+                        let expected: Vec<usize> = (0..(n + 1).saturating_sub(m))
+                            .filter(|&i| x[i..i + m] == p[..])
+                            .collect();
+                        let result: Vec<usize> = s.search_bytes(&x).collect();
+                        assert_eq!(result, expected, "x = {x:?}, p = {p:?}");
+                    }
+                }
+            }
+        }
+
+        let s = S::from_bytes(&[]);
+        assert_eq!(s.search_bytes(&[0x00, 0xff]).count(), 0);
+
+        // A pattern given as a string is searched for as its UTF-8 bytes, and a pattern
+        // given as bytes only occurs in strings where it encodes characters.
+        let s = S::new("å");
+        let result: Vec<usize> = s.search_bytes("aåbå".as_bytes()).collect();
+        assert_eq!(result, vec![1, 4]);
+        let s = S::from_bytes("å".as_bytes());
+        let result: Vec<usize> = s.search("aåbå").collect();
+        assert_eq!(result, vec![1, 3]);
+        let s = S::from_bytes(&"å".as_bytes()[1..]);
+        assert_eq!(s.search("aåbå").count(), 0);
+    }
 
     fn search_empty_x(f: SearchFn) {
         let x = "";
@@ -62,6 +253,89 @@ mod tests {
         assert_eq!(result, vec![]);
     }
 
+    /// All strings over `letters` of length `n`.
+    fn all_strings(letters: &[char], n: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        for _ in 0..n {
+            strings = strings
+                .iter()
+                .flat_map(|s| letters.iter().map(move |c| format!("{s}{c}")))
+                .collect();
+        }
+        strings
+    }
+
+    /// All byte strings over `letters` of length `n`.
+    fn all_byte_strings(letters: &[u8], n: usize) -> Vec<Vec<u8>> {
+        let mut strings = vec![vec![]];
+        for _ in 0..n {
+            strings = strings
+                .iter()
+                .flat_map(|s| letters.iter().map(move |&b| [&s[..], &[b]].concat()))
+                .collect();
+        }
+        strings
+    }
+
+    fn search_matches_naive(f: SearchFn) {
+        // Periodic patterns are the interesting cases for the shift rules, and with a
+        // binary alphabet we see plenty of them.
+        for x in all_strings(&['a', 'b'], 8) {
+            for m in 1..=4 {
+                for p in all_strings(&['a', 'b'], m) {
+                    let expected: Vec<usize> = naive(&x, &p).collect();
+                    let result: Vec<usize> = f(&x, &p).collect();
+                    assert_eq!(result, expected, "x = {x}, p = {p}");
+                }
+            }
+        }
+    }
+
+    fn search_large_alphabet(f: SearchFn) {
+        // More distinct characters than fit in u16, so the search runs on u32 characters
+        let p: String = (0..70_000)
+            .filter_map(|c| std::char::from_u32(0x4e00 + c))
+            .collect();
+        let x = format!("ab{p}{p}");
+        let m = p.chars().count();
+        let result: Vec<usize> = f(&x, &p).collect();
+        assert_eq!(result, vec![2, 2 + m]);
+    }
+
+    #[test]
+    fn search_across_threads() {
+        // Both the preprocessed pattern and the iterators it returns can be sent to other threads
+        let s = Arc::new(KMPSearch::new("aba"));
+        let handles: Vec<_> = ["ababa", "xabax"]
+            .into_iter()
+            .map(|x| {
+                let s = s.clone();
+                let matches = s.search(x);
+                std::thread::spawn(move || (matches.collect::<Vec<usize>>(), s.search(x).count()))
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, vec![(vec![0, 2], 2), (vec![1], 1)]);
+    }
+
+    fn shift_and_search(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        shift_and(x, p).unwrap()
+    }
+
+    #[test]
+    fn search_large_alphabets() {
+        search_large_alphabet(naive);
+        search_large_alphabet(kmp);
+        search_large_alphabet(bmh);
+        search_large_alphabet(bm);
+        search_large_alphabet(z_search);
+        search_large_alphabet(rabin_karp);
+        search_large_alphabet(two_way);
+        search_large_alphabet(bndm);
+        search_large_alphabet(bom);
+        search_large_alphabet(shift_and_search);
+    }
+
     macro_rules! search_tests {
         ($($test_name:ident: $search_fn:expr,)*) => {
             $(
@@ -100,6 +374,11 @@ mod tests {
                     fn [<$test_name _search_when_p_has_characters_not_in_x>]() {
                         search_when_p_has_characters_not_in_x($search_fn);
                     }
+
+                    #[test]
+                    fn [<$test_name _search_matches_naive>]() {
+                        search_matches_naive($search_fn);
+                    }
                 }
             )*
         }
@@ -112,5 +391,53 @@ mod tests {
 
     search_tests! {
         bmh_tests: bmh,
+        bm_tests: bm,
+        z_tests: z_search,
+        rabin_karp_tests: rabin_karp,
+        two_way_tests: two_way,
+        bndm_tests: bndm,
+        bom_tests: bom,
+    }
+
+    search_tests! {
+        naive_searcher_tests: searcher::<NaiveSearch>,
+        kmp_searcher_tests: searcher::<KMPSearch>,
+        bmh_searcher_tests: searcher::<BMHSearch>,
+        bm_searcher_tests: searcher::<BMSearch>,
+        z_searcher_tests: searcher::<ZSearch>,
+        rabin_karp_searcher_tests: searcher::<RabinKarpSearch>,
+        two_way_searcher_tests: searcher::<TwoWaySearch>,
+        bndm_searcher_tests: searcher::<BNDMSearch>,
+        bom_searcher_tests: searcher::<BOMSearch>,
+    }
+
+    macro_rules! searcher_tests {
+        ($($test_name:ident: $searcher:ty,)*) => {
+            $(
+                paste! {
+                    #[test]
+                    fn [<$test_name _search_reused_pattern>]() {
+                        search_reused_pattern::<$searcher>();
+                    }
+
+                    #[test]
+                    fn [<$test_name _search_bytes>]() {
+                        search_bytes::<$searcher>();
+                    }
+                }
+            )*
+        }
+    }
+
+    searcher_tests! {
+        naive_reuse_tests: NaiveSearch,
+        kmp_reuse_tests: KMPSearch,
+        bmh_reuse_tests: BMHSearch,
+        bm_reuse_tests: BMSearch,
+        z_reuse_tests: ZSearch,
+        rabin_karp_reuse_tests: RabinKarpSearch,
+        two_way_reuse_tests: TwoWaySearch,
+        bndm_reuse_tests: BNDMSearch,
+        bom_reuse_tests: BOMSearch,
     }
 }
@@ -0,0 +1,328 @@
+use super::preprocessed::{map_pattern, Algorithm, Mapped};
+use crate::utils::{CharTable, CharacterTrait, Str, StrMapper, StrMappers};
+use std::sync::Arc;
+
+/// The number of pattern positions in each block of the bit vectors.
+const WORD_SIZE: usize = u64::BITS as usize;
+
+/// Myers' bit-parallel approximate search, see [`myers`].
+struct Myers;
+
+/// A pattern preprocessed for Myers' bit-parallel approximate search.
+struct MyersPattern {
+    /// The length of the pattern
+    m: usize,
+    /// The maximal edit distance
+    k: usize,
+    /// The number of 64-bit blocks we need for the pattern
+    blocks: usize,
+    /// The match masks, with a row per block
+    peq: CharTable<u64>,
+}
+
+impl MyersPattern {
+    fn new<Char: CharacterTrait>(p: &str, k: usize, mapper: &StrMapper<Char>) -> MyersPattern {
+        let p = map_pattern(mapper, p);
+        let m = p.len();
+        let blocks = m.div_ceil(WORD_SIZE).max(1);
+        let sigma = mapper.alphabet.len() + 1;
+        // Bit i of the mask for a is set if p[i] == a. The sentinel never matches.
+        let mut peq = CharTable::new(sigma);
+        for _ in 0..blocks {
+            peq.add_row(0);
+        }
+        for (i, a) in p.iter().enumerate() {
+            let (b, a) = (i / WORD_SIZE, a.to_usize());
+            peq.set(b, a, peq.get(b, a) | 1 << (i % WORD_SIZE));
+        }
+        MyersPattern { m, k, blocks, peq }
+    }
+}
+
+struct MyersIter<Char: CharacterTrait> {
+    x: Str<Char>,
+    pattern: Arc<MyersPattern>,
+    /// The next position in the text
+    j: usize,
+    /// The positive vertical deltas for each block
+    pv: Vec<u64>,
+    /// The negative vertical deltas for each block
+    mv: Vec<u64>,
+    /// The edit distance of the whole pattern against the best substring ending at j
+    score: usize,
+}
+
+impl<Char: CharacterTrait> MyersIter<Char> {
+    fn new(x: Str<Char>, pattern: Arc<MyersPattern>) -> MyersIter<Char> {
+        // In the first column, row i has value i, so all vertical deltas are +1.
+        let pv = vec![!0u64; pattern.blocks];
+        let mv = vec![0u64; pattern.blocks];
+        let score = pattern.m;
+        MyersIter {
+            x,
+            pattern,
+            j: 0,
+            pv,
+            mv,
+            score,
+        }
+    }
+
+    /// Advances block `b` one column, given the match mask `eq` and the horizontal
+    /// delta `h_in` coming into the block's top row, and returns the horizontal delta
+    /// out of the row `last`.
+    fn advance_block(&mut self, b: usize, mut eq: u64, h_in: i8, last: u64) -> i8 {
+        let (pv, mv) = (self.pv[b], self.mv[b]);
+        let xv = eq | mv;
+        if h_in < 0 {
+            eq |= 1;
+        }
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+
+        let h_out = if ph & last != 0 {
+            1
+        } else if mh & last != 0 {
+            -1
+        } else {
+            0
+        };
+
+        ph <<= 1;
+        mh <<= 1;
+        if h_in < 0 {
+            mh |= 1;
+        } else if h_in > 0 {
+            ph |= 1;
+        }
+        self.pv[b] = mh | !(xv | ph);
+        self.mv[b] = ph & xv;
+        h_out
+    }
+}
+
+impl<Char: CharacterTrait> Iterator for MyersIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let pattern = self.pattern.clone();
+        let MyersPattern { m, k, blocks, peq } = pattern.as_ref();
+        let top_bit = 1u64 << (WORD_SIZE - 1);
+        let last_bit = 1u64 << ((m - 1) % WORD_SIZE);
+
+        while self.j < self.x.len() {
+            let a = self.x[self.j].to_usize();
+            self.j += 1;
+
+            // Row zero is zero in every column, since an occurrence can start anywhere,
+            // so there is no horizontal delta into the first block.
+            let mut h = 0;
+            for b in 0..*blocks {
+                let last = if b == blocks - 1 { last_bit } else { top_bit };
+                h = self.advance_block(b, peq.get(b, a), h, last);
+            }
+            self.score = self.score.wrapping_add_signed(h as isize);
+
+            if self.score <= *k {
+                return Some(self.j);
+            }
+        }
+        None
+    }
+}
+
+impl Algorithm for Myers {
+    /// The pattern and the maximal edit distance
+    type Input<'a> = (&'a str, usize);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = Arc<MyersPattern>;
+
+    fn preprocess<Char: CharacterTrait>(
+        (p, k): (&str, usize),
+        mapper: &StrMapper<Char>,
+    ) -> Arc<MyersPattern> {
+        Arc::new(MyersPattern::new(p, k, mapper))
+    }
+
+    fn search<Char: CharacterTrait>(
+        pattern: &Arc<MyersPattern>,
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if pattern.m == 0 {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(MyersIter::new(x, pattern.clone()))
+    }
+}
+
+/// A pattern preprocessed for Myers' bit-parallel approximate search.
+///
+/// The alphabet and the match masks of the pattern are computed once, when the
+/// `MyersSearch` is created, and can then be reused for searching any number of texts.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::MyersSearch;
+///
+/// let search = MyersSearch::new("acd", 1);
+/// let ends: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(ends, vec![5, 6, 7]);
+/// ```
+pub struct MyersSearch {
+    pattern: Mapped<Myers>,
+}
+
+impl MyersSearch {
+    /// Preprocesses the pattern `p` for search with at most `k` edits.
+    ///
+    /// The preprocessing runs in O(σ⌈m/w⌉ + m) time, where `m` is the length of the
+    /// pattern, σ is the size of its alphabet, and `w` is 64, the word size. For
+    /// large alphabets the masks only store the characters of the pattern, so they
+    /// take O(⌈m/w⌉ + m) space.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    /// * `k` - The maximal edit distance.
+    ///
+    /// # Returns
+    ///
+    /// A new `MyersSearch` instance.
+    pub fn new(p: &str, k: usize) -> MyersSearch {
+        let mapper = StrMappers::new_from_str(p);
+        let pattern = Mapped::new(mapper, (p, k));
+        MyersSearch { pattern }
+    }
+
+    /// Returns an iterator over the end positions of approximate occurrences of the
+    /// pattern in the text `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the end positions (one past the last character) of the
+    /// approximate occurrences of the pattern in `x`.
+    pub fn search(&self, x: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        self.pattern.search(x)
+    }
+}
+
+/// Returns an iterator over the end positions of approximate occurrences of the
+/// pattern `p` in the text `x` within edit distance `k`, using Myers' bit-parallel
+/// algorithm.
+///
+/// The algorithm computes the same columns as Sellers' algorithm (see
+/// [`edit`](super::edit)), but represents each column by the differences between
+/// neighbouring rows, which are all -1, 0, or +1, as bit vectors. It then computes
+/// a column from the previous with a constant number of word operations per 64
+/// rows. Patterns longer than 64 characters are split into blocks of 64 rows,
+/// with the horizontal difference carried from one block to the next.
+///
+/// The algorithm runs in O(n⌈m/w⌉) time, where `n` is the length of the text, `m`
+/// is the length of the pattern, and `w` is 64, the word size.
+///
+/// If you search for the same pattern in several texts, use [`MyersSearch`] to
+/// only preprocess the pattern once.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `k` - The maximal edit distance.
+///
+/// # Returns
+///
+/// An iterator over the end positions (one past the last character) of the
+/// approximate occurrences of `p` in `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::myers;
+///
+/// let ends: Vec<usize> = myers("abracadabra", "abr", 0).collect();
+/// assert_eq!(ends, vec![3, 10]);
+/// let ends: Vec<usize> = myers("abracadabra", "acd", 1).collect();
+/// assert_eq!(ends, vec![5, 6, 7]);
+/// ```
+pub fn myers(x: &str, p: &str, k: usize) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    MyersSearch::new(p, k).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::search::edit;
+
+    /// A simple deterministic pseudo-random DNA string.
+    fn dna(n: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                ['a', 'c', 'g', 't'][(state >> 62) as usize]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_myers_matches_edit() {
+        let x = "acgtacgttgcaacgtaggtcatgcatgcaacgt";
+        for p in ["acgt", "tgca", "ggggg", "acgtacgtac", "x", "axgt"] {
+            for k in 0..3 {
+                let expected: Vec<usize> = edit(x, p, k).collect();
+                let result: Vec<usize> = myers(x, p, k).collect();
+                assert_eq!(result, expected, "p = {p}, k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_myers_blocked_matches_edit() {
+        let x = dna(2000, 1);
+        for (m, start) in [(63, 100), (64, 200), (65, 300), (128, 400), (200, 500)] {
+            // Take the pattern from the text and mutate a few characters
+            let mut p: Vec<char> = x[start..(start + m)].chars().collect();
+            for i in (0..m).step_by(17) {
+                p[i] = 'a';
+            }
+            let p: String = p.into_iter().collect();
+            for k in [0, 3, 10, 30] {
+                let expected: Vec<usize> = edit(&x, &p, k).collect();
+                let result: Vec<usize> = myers(&x, &p, k).collect();
+                assert_eq!(result, expected, "m = {m}, k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_myers_large_alphabet() {
+        // Too many characters for dense match masks
+        let x: String = (0..600)
+            .map(|i| std::char::from_u32(0x4e00 + (i * 7) % 300).unwrap())
+            .collect();
+        let p: String = x.chars().skip(250).take(300).collect();
+        for k in [0, 2] {
+            let expected: Vec<usize> = edit(&x, &p, k).collect();
+            let result: Vec<usize> = myers(&x, &p, k).collect();
+            assert_eq!(result, expected, "k = {k}");
+        }
+    }
+
+    #[test]
+    fn test_myers_reused() {
+        let search = MyersSearch::new("abc", 1);
+        assert_eq!(search.search("xabcx").collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(search.search("zzz").count(), 0);
+    }
+}
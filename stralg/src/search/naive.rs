@@ -1,41 +1,80 @@
-use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
 
-struct NaiveSearch<Char: CharacterTrait> {
-    x: Str<Char>,
-    p: Str<Char>,
+/// The naive string matching algorithm, see [`naive`].
+pub struct Naive;
+
+/// A pattern prepared for naive search.
+pub struct NaivePattern<Char: CharacterTrait> {
+    /// The pattern we are searching for
+    p: Vec<Char>,
+}
+
+struct NaiveIter<Char: CharacterTrait, X> {
+    x: X,
+    pattern: Arc<NaivePattern<Char>>,
     i: usize,
 }
 
-impl<Char: CharacterTrait> Iterator for NaiveSearch<Char> {
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for NaiveIter<Char, X> {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let NaiveSearch { x, p, i } = self;
+        let NaiveIter { x, pattern, i } = self;
+        let x = x.as_ref();
+        let p = &pattern.p;
         let n = x.len();
         let m = p.len();
         for j in *i..=(n - m) {
-            if &x[j..(j + m)] == &p[..] {
+            if x[j..(j + m)] == p[..] {
                 *i = j + 1;
                 return Some(j);
             }
         }
+        *i = n - m + 1;
         None
     }
 }
 
-fn naive_impl<Char>(x: &str, p: &str, mapper: StrMapper<Char>) -> Box<dyn Iterator<Item = usize>>
-where
-    Char: CharacterTrait,
-{
-    let x = mapper.map_str(x).unwrap();
-    let p = match mapper.map_str(p) {
-        Ok(p) => p,
-        Err(_) => return Box::new(std::iter::empty()),
-    };
+impl ExactAlgorithm for Naive {
+    type Pattern<Char: CharacterTrait> = NaivePattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], _sigma: usize) -> NaivePattern<Char> {
+        NaivePattern { p: p.to_vec() }
+    }
 
-    Box::new(NaiveSearch { x, p, i: 0 })
+    fn search<'a, Char, X>(
+        pattern: Arc<NaivePattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(NaiveIter { x, pattern, i: 0 })
+    }
 }
 
+/// A pattern prepared for the naive string matching algorithm.
+///
+/// The naive algorithm does not preprocess the pattern, but the pattern's alphabet is
+/// still only built once, when the `NaiveSearch` is created, and can then be reused
+/// for searching any number of texts.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{NaiveSearch, Searcher};
+///
+/// let search = NaiveSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type NaiveSearch = Preprocessed<Naive>;
+
 /// Returns an iterator over the starting indices of occurrences of the pattern
 /// `p` in the text `x` using the naive string matching algorithm.
 ///
@@ -47,6 +86,9 @@ where
 /// The algorithm runs in worst time O((n - m + 1) * m) and best time O(n - m + 1),
 /// where `n` is the length of the text and `m` is the length of the pattern.
 ///
+/// If you search for the same pattern in several texts, use [`NaiveSearch`] to
+/// only build the pattern's alphabet once.
+///
 /// # Arguments
 ///
 /// * `x` - The text in which to search for the pattern.
@@ -85,17 +127,9 @@ where
 /// let matches: Vec<usize> = naive(text, pattern).collect();
 /// assert_eq!(matches, vec![2]);
 /// ```
-pub fn naive(x: &str, p: &str) -> Box<dyn Iterator<Item = usize>> {
+pub fn naive(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
     if x.is_empty() || p.is_empty() {
         return Box::new(std::iter::empty());
     }
-    if x.len() < p.len() {
-        return Box::new(std::iter::empty());
-    }
-
-    let mapper = StrMappers::new_from_str(x).unwrap(); // We unwrap because we don't expect alphabet larger than u16
-    match mapper {
-        StrMappers::U8Mapper(mapper) => naive_impl(x, p, mapper),
-        StrMappers::U16Mapper(mapper) => naive_impl(x, p, mapper),
-    }
+    NaiveSearch::new(p).search(x)
 }
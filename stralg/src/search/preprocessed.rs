@@ -0,0 +1,282 @@
+use super::{ByteSearcher, MatchIter, Searcher};
+use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock};
+
+/// An algorithm that preprocesses its input for texts over any character type.
+///
+/// We only learn if strings are mapped to `u8`, `u16` or `u32` characters when we
+/// have built the alphabet, so the preprocessed input is kept in a [`Mapped`] that
+/// holds it for whichever character type the alphabet needed.
+pub(crate) trait Algorithm {
+    /// What we preprocess, e.g. the pattern and the number of errors we allow.
+    type Input<'a>: Copy;
+    /// What the search reports for each occurrence.
+    type Item;
+    /// The preprocessed input for texts over `Char`.
+    type Pattern<Char: CharacterTrait>: Send + Sync;
+
+    /// Preprocesses `input` for texts mapped with `mapper`.
+    fn preprocess<Char: CharacterTrait>(
+        input: Self::Input<'_>,
+        mapper: &StrMapper<Char>,
+    ) -> Self::Pattern<Char>;
+
+    /// Returns an iterator over the occurrences of the pattern in the mapped text `x`.
+    fn search<Char: CharacterTrait>(
+        pattern: &Self::Pattern<Char>,
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + Send>;
+
+    /// Maps the text `x` to the alphabet of the pattern.
+    fn map_text<Char: CharacterTrait>(
+        _pattern: &Self::Pattern<Char>,
+        mapper: &StrMapper<Char>,
+        x: &str,
+    ) -> Str<Char> {
+        // Characters that are not in the pattern are mapped to the sentinel, which never matches.
+        mapper.map_str_lossy(x)
+    }
+}
+
+/// Maps a pattern to the alphabet we built from it.
+pub(crate) fn map_pattern<Char: CharacterTrait>(mapper: &StrMapper<Char>, p: &str) -> Str<Char> {
+    mapper.map_str(p).unwrap() // We built the alphabet from p so this cannot fail...
+}
+
+/// The input of an [`Algorithm`] preprocessed for the character type of its alphabet.
+pub(crate) enum Mapped<A: Algorithm> {
+    U8(StrMapper<u8>, A::Pattern<u8>),
+    U16(StrMapper<u16>, A::Pattern<u16>),
+    U32(StrMapper<u32>, A::Pattern<u32>),
+}
+
+impl<A: Algorithm> Mapped<A> {
+    /// Preprocesses `input` for the character type that `mappers` picked.
+    pub(crate) fn new(mappers: StrMappers, input: A::Input<'_>) -> Mapped<A> {
+        match mappers {
+            StrMappers::U8Mapper(mapper) => {
+                let pattern = A::preprocess(input, &mapper);
+                Mapped::U8(mapper, pattern)
+            }
+            StrMappers::U16Mapper(mapper) => {
+                let pattern = A::preprocess(input, &mapper);
+                Mapped::U16(mapper, pattern)
+            }
+            StrMappers::U32Mapper(mapper) => {
+                let pattern = A::preprocess(input, &mapper);
+                Mapped::U32(mapper, pattern)
+            }
+        }
+    }
+
+    /// Maps the text `x` and searches it with `A`.
+    pub(crate) fn search(&self, x: &str) -> Box<dyn Iterator<Item = A::Item> + Send> {
+        self.search_as::<A>(x)
+    }
+
+    /// Maps the text `x` and searches it with `B`, which shares the preprocessing of `A`.
+    pub(crate) fn search_as<B>(&self, x: &str) -> Box<dyn Iterator<Item = B::Item> + Send>
+    where
+        B: Algorithm<
+            Pattern<u8> = A::Pattern<u8>,
+            Pattern<u16> = A::Pattern<u16>,
+            Pattern<u32> = A::Pattern<u32>,
+        >,
+    {
+        match self {
+            Mapped::U8(mapper, pattern) => B::search(pattern, B::map_text(pattern, mapper, x)),
+            Mapped::U16(mapper, pattern) => B::search(pattern, B::map_text(pattern, mapper, x)),
+            Mapped::U32(mapper, pattern) => B::search(pattern, B::map_text(pattern, mapper, x)),
+        }
+    }
+}
+
+/// An exact search algorithm for a single pattern.
+///
+/// The algorithms only see the pattern and the texts as slices of ranks, so they
+/// work for any character type, and [`Preprocessed`] takes care of building the
+/// alphabet and mapping strings to it.
+pub trait ExactAlgorithm: 'static {
+    /// The pattern preprocessed for texts over `Char`.
+    type Pattern<Char: CharacterTrait>: Send + Sync + 'static;
+
+    /// Preprocesses the pattern `p`.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for. It is never empty.
+    /// * `sigma` - The size of the alphabet, so all characters, in the pattern and
+    ///   in the texts, are less than `sigma`.
+    ///
+    /// # Returns
+    ///
+    /// The preprocessed pattern.
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> Self::Pattern<Char>;
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern in
+    /// the text `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The preprocessed pattern.
+    /// * `x` - The text in which to search for the pattern. It is never shorter than
+    ///   the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern in `x`,
+    /// in increasing order.
+    fn search<'a, Char, X>(
+        pattern: Arc<Self::Pattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a;
+}
+
+/// Runs an [`ExactAlgorithm`] as an [`Algorithm`] over mapped strings.
+struct Exact<E>(PhantomData<E>);
+
+impl<E: ExactAlgorithm> Algorithm for Exact<E> {
+    type Input<'a> = &'a str;
+    type Item = usize;
+    /// The length of the pattern and the pattern preprocessed by `E`
+    type Pattern<Char: CharacterTrait> = (usize, Arc<E::Pattern<Char>>);
+
+    fn preprocess<Char: CharacterTrait>(
+        p: &str,
+        mapper: &StrMapper<Char>,
+    ) -> (usize, Arc<E::Pattern<Char>>) {
+        let p = map_pattern(mapper, p);
+        let sigma = mapper.alphabet.len() + 1;
+        (p.len(), Arc::new(E::preprocess(&p[..], sigma)))
+    }
+
+    fn search<Char: CharacterTrait>(
+        (m, pattern): &(usize, Arc<E::Pattern<Char>>),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if x.len() < *m {
+            return Box::new(std::iter::empty());
+        }
+        E::search(pattern.clone(), x)
+    }
+}
+
+/// A pattern preprocessed once by the exact search algorithm `E`, so it can be
+/// searched for in any number of texts through the [`Searcher`] trait.
+///
+/// Each algorithm has an alias for its `Preprocessed`, such as
+/// [`KMPSearch`](super::KMPSearch).
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{KMPSearch, Searcher};
+///
+/// let search = KMPSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub struct Preprocessed<E: ExactAlgorithm> {
+    /// The pattern preprocessed for strings, or `None` if the pattern is empty or
+    /// is not valid UTF-8, so it never occurs in a string
+    pattern: Option<Mapped<Exact<E>>>,
+    /// The length of the pattern in characters
+    m: usize,
+    /// The bytes of the pattern
+    bytes: Box<[u8]>,
+    /// The pattern preprocessed for bytes, where each byte is its own character.
+    /// It is built the first time we search bytes.
+    byte_pattern: OnceLock<Arc<E::Pattern<u8>>>,
+}
+
+impl<E: ExactAlgorithm> Preprocessed<E> {
+    /// Preprocesses the pattern `p` for strings, if it is valid UTF-8.
+    fn from_parts(p: Option<&str>, bytes: &[u8]) -> Preprocessed<E> {
+        let p = p.filter(|p| !p.is_empty());
+        let pattern = p.map(|p| Mapped::new(StrMappers::new_from_str(p), p));
+        Preprocessed {
+            pattern,
+            m: p.map_or(0, |p| p.chars().count()),
+            bytes: bytes.into(),
+            byte_pattern: OnceLock::new(),
+        }
+    }
+
+    /// Returns an iterator over the occurrences of the pattern in the text `x`, with
+    /// both their character indices and their byte offsets.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the occurrences of the pattern in `x` as [`Match`](super::Match)es,
+    /// in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::search::{BMHSearch, Searcher};
+    ///
+    /// let x = "blåbærsyltetøy og blåbær";
+    /// let search = BMHSearch::new("bær");
+    /// let matches: Vec<_> = search.search_matches(x).collect();
+    /// assert_eq!(matches[1].range(), 21..24);
+    /// assert_eq!(&x[matches[1].byte_range()], "bær");
+    /// ```
+    pub fn search_matches<'a>(
+        &self,
+        x: &'a str,
+    ) -> MatchIter<'a, Box<dyn Iterator<Item = usize> + Send>> {
+        MatchIter::new(x, self.m, self.search(x))
+    }
+
+    /// Returns the pattern preprocessed for bytes. The bytes are the characters, so
+    /// the alphabet has all 256 of them and we never map the texts.
+    fn byte_pattern(&self) -> &Arc<E::Pattern<u8>> {
+        self.byte_pattern
+            .get_or_init(|| Arc::new(E::preprocess(&self.bytes, 256)))
+    }
+}
+
+impl<E: ExactAlgorithm> Searcher for Preprocessed<E> {
+    fn new(p: &str) -> Preprocessed<E> {
+        Preprocessed::from_parts(Some(p), p.as_bytes())
+    }
+
+    fn search(&self, x: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        match &self.pattern {
+            Some(pattern) => pattern.search(x),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+impl<E: ExactAlgorithm> ByteSearcher for Preprocessed<E> {
+    fn from_bytes(p: &[u8]) -> Preprocessed<E> {
+        // Occurrences in a string start and end at character boundaries, so if the bytes
+        // are valid UTF-8 we search for the characters they encode, and if they are not
+        // the pattern never occurs in a string.
+        let preprocessed = Preprocessed::from_parts(std::str::from_utf8(p).ok(), p);
+        if !p.is_empty() {
+            // We expect to search bytes with a pattern given as bytes, so we preprocess
+            // it for bytes right away.
+            preprocessed.byte_pattern();
+        }
+        preprocessed
+    }
+
+    fn search_bytes<'a>(&self, x: &'a [u8]) -> Box<dyn Iterator<Item = usize> + Send + 'a> {
+        if self.bytes.is_empty() || x.len() < self.bytes.len() {
+            return Box::new(std::iter::empty());
+        }
+        E::search(self.byte_pattern().clone(), x)
+    }
+}
@@ -0,0 +1,483 @@
+use super::preprocessed::{map_pattern, Algorithm, Mapped};
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::{CharacterTrait, Str, StrMapper, StrMappers};
+use crate::Error;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+
+/// An iterator over `(pattern_id, position)` pairs.
+type MultiMatches = Box<dyn Iterator<Item = (usize, usize)> + Send>;
+
+/// The Mersenne prime 2^61 - 1, which we compute hashes modulo.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// Computes `a * b` modulo `MODULUS`.
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+/// Picks a random base in `[sigma, MODULUS)`.
+fn random_base(sigma: u64) -> u64 {
+    // A RandomState has random keys, so the hash of an empty input is a random number.
+    let r = RandomState::new().build_hasher().finish();
+    sigma + r % (MODULUS - sigma)
+}
+
+/// A polynomial rolling hash over character ranks, for windows of length `m`.
+///
+/// The hash of `a_0 a_1 ... a_{m-1}` is `a_0 B^{m-1} + a_1 B^{m-2} + ... + a_{m-1}`
+/// modulo `MODULUS`. The base `B` is picked at random from `[σ, MODULUS)`, where σ
+/// is one larger than the largest rank. Since the ranks are smaller than the base,
+/// different strings only collide through the modulus, and since the base is random,
+/// two different windows collide with probability at most `m / MODULUS`, whatever
+/// the input.
+struct RollingHash {
+    /// The base of the polynomial
+    base: u64,
+    /// `B^{m-1}`, the weight of the first character in a window
+    high: u64,
+}
+
+impl RollingHash {
+    fn new(sigma: usize, m: usize) -> RollingHash {
+        let base = random_base(sigma as u64);
+        let high = (1..m).fold(1, |high, _| mul_mod(high, base));
+        RollingHash { base, high }
+    }
+
+    /// The hash of `s`.
+    fn hash<Char: CharacterTrait>(&self, s: &[Char]) -> u64 {
+        s.iter().fold(0, |h, &a| {
+            (mul_mod(h, self.base) + a.to_usize() as u64) % MODULUS
+        })
+    }
+
+    /// The hash of the window we get by removing `out` from the front and adding
+    /// `inn` to the back of the window with hash `h`.
+    fn roll<Char: CharacterTrait>(&self, h: u64, out: Char, inn: Char) -> u64 {
+        let h = (h + MODULUS - mul_mod(out.to_usize() as u64, self.high)) % MODULUS;
+        (mul_mod(h, self.base) + inn.to_usize() as u64) % MODULUS
+    }
+}
+
+/// The state of a window sliding over a text.
+struct Window<X> {
+    /// The text we are searching in
+    x: X,
+    /// The length of the window
+    m: usize,
+    /// The start of the next window to check
+    j: usize,
+    /// The hash of the window starting at `j`
+    h: u64,
+}
+
+impl<X> Window<X> {
+    fn new<Char: CharacterTrait>(x: X, m: usize, hash: &RollingHash) -> Window<X>
+    where
+        X: AsRef<[Char]>,
+    {
+        let h = hash.hash(&x.as_ref()[..m]);
+        Window { x, m, j: 0, h }
+    }
+
+    /// Returns the start and the hash of the current window and moves the window
+    /// one position to the right, or returns `None` if we are past the end of the text.
+    fn next<Char: CharacterTrait>(&mut self, hash: &RollingHash) -> Option<(usize, u64)>
+    where
+        X: AsRef<[Char]>,
+    {
+        let Window { x, m, j, h } = self;
+        let x = x.as_ref();
+        if *j + *m > x.len() {
+            return None;
+        }
+        let current = (*j, *h);
+        if *j + *m < x.len() {
+            *h = hash.roll(*h, x[*j], x[*j + *m]);
+        }
+        *j += 1;
+        Some(current)
+    }
+}
+
+/// The Rabin-Karp string matching algorithm, see [`rabin_karp`].
+pub struct RabinKarp;
+
+/// A pattern preprocessed for Rabin-Karp search.
+pub struct RKPattern<Char: CharacterTrait> {
+    /// The pattern we are searching for
+    p: Vec<Char>,
+    /// The rolling hash for windows the length of the pattern
+    hash: RollingHash,
+    /// The hash of the pattern
+    p_hash: u64,
+}
+
+struct RKIter<Char: CharacterTrait, X> {
+    /// The window over the text we are searching in
+    window: Window<X>,
+    /// The preprocessed pattern we are searching for
+    pattern: Arc<RKPattern<Char>>,
+}
+
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for RKIter<Char, X> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let RKIter { window, pattern } = self;
+        let RKPattern { p, hash, p_hash } = pattern.as_ref();
+        while let Some((j, h)) = window.next(hash) {
+            // Verify the hit, so hash collisions are never reported
+            if h == *p_hash && window.x.as_ref()[j..j + p.len()] == p[..] {
+                return Some(j);
+            }
+        }
+        None
+    }
+}
+
+impl ExactAlgorithm for RabinKarp {
+    type Pattern<Char: CharacterTrait> = RKPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], sigma: usize) -> RKPattern<Char> {
+        let hash = RollingHash::new(sigma, p.len());
+        let p_hash = hash.hash(p);
+        RKPattern {
+            p: p.to_vec(),
+            hash,
+            p_hash,
+        }
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<RKPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        let window = Window::new(x, pattern.p.len(), &pattern.hash);
+        Box::new(RKIter { window, pattern })
+    }
+}
+
+/// A pattern preprocessed for the Rabin-Karp string matching algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{RabinKarpSearch, Searcher};
+///
+/// let search = RabinKarpSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type RabinKarpSearch = Preprocessed<RabinKarp>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Rabin-Karp algorithm.
+///
+/// The algorithm slides a window the length of the pattern over the text and
+/// maintains a rolling hash of the alphabet ranks in the window. Only windows with
+/// the same hash as the pattern are compared to it, so hash collisions never give
+/// false positives.
+///
+/// The algorithm runs in O(n + m) expected time and O(nm) worst-case time, where
+/// `n` is the length of the text and `m` is the length of the pattern. The
+/// expectation is over the random base of the hash, so it holds for any input.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::rabin_karp;
+///
+/// let matches: Vec<usize> = rabin_karp("abracadabra", "abr").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn rabin_karp(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    RabinKarpSearch::new(p).search(x)
+}
+
+/// Multi-pattern Rabin-Karp search, see [`RabinKarpMulti`].
+struct RKMulti;
+
+/// A set of equal-length patterns preprocessed for Rabin-Karp search.
+struct RKMultiPatterns<Char: CharacterTrait> {
+    /// The patterns we are searching for
+    patterns: Vec<Str<Char>>,
+    /// The length of the patterns
+    m: usize,
+    /// The rolling hash for windows the length of the patterns
+    hash: RollingHash,
+    /// The indices of the patterns with each hash value
+    table: HashMap<u64, Vec<usize>>,
+}
+
+struct RKMultiIter<Char: CharacterTrait> {
+    /// The window over the text we are searching in
+    window: Window<Str<Char>>,
+    /// The preprocessed patterns we are searching for
+    patterns: Arc<RKMultiPatterns<Char>>,
+    /// The verified hits at the current window that we haven't reported yet
+    hits: std::vec::IntoIter<(usize, usize)>,
+}
+
+impl<Char: CharacterTrait> Iterator for RKMultiIter<Char> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let RKMultiIter {
+            window,
+            patterns,
+            hits,
+        } = self;
+        let RKMultiPatterns {
+            patterns: ps,
+            m,
+            hash,
+            table,
+        } = patterns.as_ref();
+        loop {
+            if let Some(hit) = hits.next() {
+                return Some(hit);
+            }
+            let (j, h) = window.next(hash)?;
+            if let Some(ids) = table.get(&h) {
+                // Verify the candidates, so hash collisions are never reported
+                let w = &window.x[j..j + m];
+                *hits = ids
+                    .iter()
+                    .filter(|&&id| ps[id][..] == *w)
+                    .map(|&id| (id, j))
+                    .collect::<Vec<_>>()
+                    .into_iter();
+            }
+        }
+    }
+}
+
+impl Algorithm for RKMulti {
+    /// The patterns and their common length
+    type Input<'a> = (&'a [&'a str], usize);
+    type Item = (usize, usize);
+    type Pattern<Char: CharacterTrait> = Arc<RKMultiPatterns<Char>>;
+
+    fn preprocess<Char: CharacterTrait>(
+        (patterns, m): (&[&str], usize),
+        mapper: &StrMapper<Char>,
+    ) -> Arc<RKMultiPatterns<Char>> {
+        let patterns: Vec<Str<Char>> = patterns.iter().map(|p| map_pattern(mapper, p)).collect();
+        let hash = RollingHash::new(mapper.alphabet.len() + 1, m);
+        let mut table: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (id, p) in patterns.iter().enumerate() {
+            table.entry(hash.hash(&p[..])).or_default().push(id);
+        }
+        Arc::new(RKMultiPatterns {
+            patterns,
+            m,
+            hash,
+            table,
+        })
+    }
+
+    fn search<Char: CharacterTrait>(
+        patterns: &Arc<RKMultiPatterns<Char>>,
+        x: Str<Char>,
+    ) -> MultiMatches {
+        if patterns.m == 0 || patterns.patterns.is_empty() || x.len() < patterns.m {
+            return Box::new(std::iter::empty());
+        }
+        let window = Window::new(x, patterns.m, &patterns.hash);
+        Box::new(RKMultiIter {
+            window,
+            patterns: patterns.clone(),
+            hits: Vec::new().into_iter(),
+        })
+    }
+}
+
+/// A set of equal-length patterns preprocessed for multi-pattern Rabin-Karp search.
+///
+/// The hashes of all the patterns are kept in a hash table, so each window in the
+/// text is only compared to the patterns with the same hash as the window.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::RabinKarpMulti;
+///
+/// let search = RabinKarpMulti::new(&["abr", "cad", "bra"]).unwrap();
+/// let matches: Vec<(usize, usize)> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![(0, 0), (2, 1), (1, 4), (0, 7), (2, 8)]);
+/// ```
+pub struct RabinKarpMulti {
+    patterns: Mapped<RKMulti>,
+}
+
+impl RabinKarpMulti {
+    /// Preprocesses the patterns `patterns` for multi-pattern Rabin-Karp search.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The patterns to search for. They must all have the same length.
+    ///
+    /// # Returns
+    ///
+    /// A new `RabinKarpMulti` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::PatternLengthMismatch`] if the patterns do not all have the
+    /// same length.
+    pub fn new(patterns: &[&str]) -> Result<RabinKarpMulti, Error> {
+        let lengths: Vec<usize> = patterns.iter().map(|p| p.chars().count()).collect();
+        let m = lengths.first().copied().unwrap_or(0);
+        if let Some((index, &length)) = lengths.iter().enumerate().find(|(_, &len)| len != m) {
+            return Err(Error::PatternLengthMismatch {
+                index,
+                length,
+                expected: m,
+            });
+        }
+        let mapper = StrMappers::new_from_strs(patterns);
+        let patterns = Mapped::new(mapper, (patterns, m));
+        Ok(RabinKarpMulti { patterns })
+    }
+
+    /// Returns an iterator over the occurrences of the patterns in the text `x`.
+    ///
+    /// Each occurrence is reported as a pair of the pattern's index in the slice
+    /// the search was built from and the starting index of the occurrence in `x`.
+    /// Occurrences are reported in order of their positions, and occurrences at
+    /// the same position in order of the pattern indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the patterns.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over `(pattern_id, position)` pairs.
+    pub fn search(&self, x: &str) -> Box<dyn Iterator<Item = (usize, usize)> + Send> {
+        self.patterns.search(x)
+    }
+}
+
+/// Returns an iterator over the occurrences of the equal-length patterns
+/// `patterns` in the text `x` using the Rabin-Karp algorithm.
+///
+/// The algorithm runs in O(n + km) expected time, where `n` is the length of the
+/// text, `k` the number of patterns, and `m` their length, plus the time for
+/// verifying the hits. The expectation is over the random base of the hash, so it
+/// holds for any input.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the patterns.
+/// * `patterns` - The patterns to search for. They must all have the same length.
+///
+/// # Returns
+///
+/// An iterator over `(pattern_id, position)` pairs, see [`RabinKarpMulti::search`].
+///
+/// # Errors
+///
+/// Returns [`Error::PatternLengthMismatch`] if the patterns do not all have the
+/// same length.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::rabin_karp_multi;
+///
+/// let matches: Vec<(usize, usize)> = rabin_karp_multi("ushers", &["she", "her"])
+///     .unwrap()
+///     .collect();
+/// assert_eq!(matches, vec![(0, 1), (1, 2)]);
+/// ```
+pub fn rabin_karp_multi(x: &str, patterns: &[&str]) -> Result<MultiMatches, Error> {
+    Ok(RabinKarpMulti::new(patterns)?.search(x))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    #[test]
+    fn test_rolling_hash() {
+        let hash = RollingHash::new(4, 3);
+        let x: Vec<u8> = vec![1, 2, 3, 1, 2, 0, 3];
+        let mut h = hash.hash(&x[0..3]);
+        for j in 1..=x.len() - 3 {
+            h = hash.roll(h, x[j - 1], x[j + 2]);
+            assert_eq!(h, hash.hash(&x[j..j + 3]));
+        }
+    }
+
+    #[test]
+    fn test_collisions_are_verified() {
+        // The ranks of "ab" and "abbab" over the alphabet {a, b}
+        let mut pattern = RabinKarp::preprocess(&[1u8, 2], 3);
+        // With base zero, the hash of a window is the rank of its last character,
+        // so "bb" collides with the pattern.
+        pattern.hash = RollingHash { base: 0, high: 0 };
+        pattern.p_hash = pattern.hash.hash(&pattern.p[..]);
+        let result: Vec<usize> =
+            RabinKarp::search(Arc::new(pattern), vec![1u8, 2, 2, 1, 2]).collect();
+        assert_eq!(result, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_multi_matches_naive() {
+        let x = "aabaabaaabaabbaabaaab";
+        let patterns = ["aab", "baa", "aab", "bbb"];
+        let mut expected: Vec<(usize, usize)> = patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(id, p)| naive(x, p).map(move |j| (id, j)))
+            .collect();
+        expected.sort_by_key(|&(id, j)| (j, id));
+        let result: Vec<(usize, usize)> = rabin_karp_multi(x, &patterns).unwrap().collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_multi_unequal_lengths() {
+        assert_eq!(
+            RabinKarpMulti::new(&["ab", "ba", "abc"]).err(),
+            Some(Error::PatternLengthMismatch {
+                index: 2,
+                length: 3,
+                expected: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_empty() {
+        let result: Vec<(usize, usize)> = rabin_karp_multi("abc", &[]).unwrap().collect();
+        assert_eq!(result, vec![]);
+        let result: Vec<(usize, usize)> = rabin_karp_multi("", &["a"]).unwrap().collect();
+        assert_eq!(result, vec![]);
+    }
+}
@@ -0,0 +1,528 @@
+use super::preprocessed::{Algorithm, Mapped};
+use crate::utils::{Alphabet, CharTable, CharacterTrait, Str, StrMapper, StrMappers};
+use crate::Error;
+use std::sync::Arc;
+
+/// The number of pattern positions in each word of the bit masks.
+const WORD_SIZE: usize = u64::BITS as usize;
+
+/// The characters a single position in a pattern can match.
+#[derive(Debug, PartialEq)]
+enum CharClass {
+    /// Matches any character (`.`)
+    Any,
+    /// Matches the characters in the set (`a` or `[ac]`), or, if negated, the
+    /// characters not in the set (`[^ac]`)
+    Set { chars: Vec<char>, negated: bool },
+}
+
+/// Parses a pattern with character classes into the classes of each position.
+///
+/// The pattern syntax is: `.` matches any character, `[abc]` matches one of the
+/// characters in the brackets, `[^abc]` matches any character not in the brackets,
+/// and `\c` matches `c` literally, so special characters can be escaped both inside
+/// and outside brackets. Any other character matches itself.
+fn parse_pattern(p: &str) -> Result<Vec<CharClass>, Error> {
+    let mut classes = Vec::new();
+    let mut chars = p.chars().enumerate();
+    while let Some((i, c)) = chars.next() {
+        let class = match c {
+            '.' => CharClass::Any,
+            '\\' => match chars.next() {
+                Some((_, c)) => CharClass::Set {
+                    chars: vec![c],
+                    negated: false,
+                },
+                None => return Err(Error::DanglingEscape { position: i }),
+            },
+            '[' => {
+                let mut set = Vec::new();
+                let mut negated = false;
+                let mut first = true;
+                loop {
+                    match chars.next() {
+                        None => return Err(Error::UnterminatedCharacterClass { position: i }),
+                        Some((_, '^')) if first => negated = true,
+                        Some((_, ']')) => break,
+                        Some((j, '\\')) => match chars.next() {
+                            Some((_, c)) => set.push(c),
+                            None => return Err(Error::DanglingEscape { position: j }),
+                        },
+                        Some((_, c)) => set.push(c),
+                    }
+                    first = false;
+                }
+                if set.is_empty() {
+                    return Err(Error::EmptyCharacterClass { position: i });
+                }
+                CharClass::Set {
+                    chars: set,
+                    negated,
+                }
+            }
+            c => CharClass::Set {
+                chars: vec![c],
+                negated: false,
+            },
+        };
+        classes.push(class);
+    }
+    Ok(classes)
+}
+
+/// Builds the match masks for the classes. Bit `i` of the mask for character `a`
+/// is set if position `i` in the pattern matches `a`, and the masks have a row per
+/// word.
+fn build_masks(classes: &[CharClass], alphabet: &Alphabet, words: usize) -> CharTable<u64> {
+    // The sentinel represents all the characters that are not in the alphabet, and
+    // the characters that a class does not mention match it like the sentinel does,
+    // so the sentinel's masks are the defaults of the rows.
+    let mut defaults = vec![0u64; words];
+    for (i, class) in classes.iter().enumerate() {
+        if matches!(class, CharClass::Any | CharClass::Set { negated: true, .. }) {
+            defaults[i / WORD_SIZE] |= 1u64 << (i % WORD_SIZE);
+        }
+    }
+    let mut masks = CharTable::new(alphabet.len() + 1);
+    for &default in &defaults {
+        masks.add_row(default);
+    }
+    for (i, class) in classes.iter().enumerate() {
+        let (word, bit) = (i / WORD_SIZE, 1u64 << (i % WORD_SIZE));
+        if let CharClass::Set { chars, negated } = class {
+            for &c in chars {
+                let a = alphabet.index(c).unwrap(); // All pattern characters are in the alphabet
+                let mask = masks.get(word, a);
+                masks.set(word, a, if *negated { mask & !bit } else { mask | bit });
+            }
+        }
+    }
+    masks
+}
+
+/// Shift-And search, see [`shift_and`].
+struct ShiftAnd;
+
+/// Shift-Or search, see [`shift_or`].
+struct ShiftOr;
+
+/// A pattern with character classes preprocessed for bit-parallel search.
+struct ShiftPattern {
+    /// The number of positions in the pattern
+    m: usize,
+    /// The number of words in the bit masks
+    words: usize,
+    /// The Shift-And match masks, with a row per word
+    masks: CharTable<u64>,
+}
+
+impl ShiftPattern {
+    fn new(classes: &[CharClass], alphabet: &Alphabet) -> ShiftPattern {
+        let m = classes.len();
+        let words = m.div_ceil(WORD_SIZE).max(1);
+        let masks = build_masks(classes, alphabet, words);
+        ShiftPattern { m, words, masks }
+    }
+}
+
+/// The Shift-And state machine, where bit `i` of the state is set if `p[..=i]`
+/// matches the text that ends at the current position.
+struct ShiftAndIter<Char: CharacterTrait> {
+    x: Str<Char>,
+    pattern: Arc<ShiftPattern>,
+    /// The next position in the text
+    j: usize,
+    /// The state, one bit per pattern position
+    state: Vec<u64>,
+}
+
+impl<Char: CharacterTrait> Iterator for ShiftAndIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let ShiftAndIter {
+            x,
+            pattern,
+            j,
+            state,
+        } = self;
+        let ShiftPattern { m, masks, .. } = pattern.as_ref();
+        let (last_word, last_bit) = ((m - 1) / WORD_SIZE, 1u64 << ((m - 1) % WORD_SIZE));
+        while *j < x.len() {
+            let a = x[*j].to_usize();
+            *j += 1;
+            // Shift the state one position, with a one shifted into the first
+            // position since the empty prefix always matches.
+            let mut carry = 1;
+            for (w, word) in state.iter_mut().enumerate() {
+                let next_carry = *word >> (WORD_SIZE - 1);
+                let shifted = (*word << 1) | carry;
+                // Words with no active positions stay empty, so we skip the mask lookup
+                *word = if shifted == 0 {
+                    0
+                } else {
+                    shifted & masks.get(w, a)
+                };
+                carry = next_carry;
+            }
+            if state[last_word] & last_bit != 0 {
+                return Some(*j - m);
+            }
+        }
+        None
+    }
+}
+
+/// The Shift-Or state machine, which is Shift-And with the bits complemented, so
+/// bit `i` of the state is cleared if `p[..=i]` matches. This saves the operation
+/// that shifts in the one.
+struct ShiftOrIter<Char: CharacterTrait> {
+    x: Str<Char>,
+    pattern: Arc<ShiftPattern>,
+    /// The next position in the text
+    j: usize,
+    /// The state, one bit per pattern position
+    state: Vec<u64>,
+}
+
+impl<Char: CharacterTrait> Iterator for ShiftOrIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let ShiftOrIter {
+            x,
+            pattern,
+            j,
+            state,
+        } = self;
+        let ShiftPattern { m, masks, .. } = pattern.as_ref();
+        let (last_word, last_bit) = ((m - 1) / WORD_SIZE, 1u64 << ((m - 1) % WORD_SIZE));
+        while *j < x.len() {
+            let a = x[*j].to_usize();
+            *j += 1;
+            let mut carry = 0;
+            for (w, word) in state.iter_mut().enumerate() {
+                let next_carry = *word >> (WORD_SIZE - 1);
+                let shifted = (*word << 1) | carry;
+                // Words with no active positions stay full, so we skip the mask lookup
+                *word = if shifted == !0 {
+                    !0
+                } else {
+                    shifted | !masks.get(w, a)
+                };
+                carry = next_carry;
+            }
+            if state[last_word] & last_bit == 0 {
+                return Some(*j - m);
+            }
+        }
+        None
+    }
+}
+
+/// A pattern with character classes preprocessed for Shift-And and Shift-Or search.
+///
+/// Patterns can contain `.`, which matches any character, `[abc]`, which matches
+/// any of the characters in the brackets, and `[^abc]`, which matches any character
+/// not in the brackets. Use `\` to match the special characters `.`, `[`, `]`, `^`
+/// and `\` literally.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::ShiftAndSearch;
+///
+/// let search = ShiftAndSearch::new("[ac]g.t").unwrap();
+/// let matches: Vec<usize> = search.search("aggtcgatttgt").collect();
+/// assert_eq!(matches, vec![0, 4]);
+/// let matches: Vec<usize> = search.search_shift_or("aggtcgatttgt").collect();
+/// assert_eq!(matches, vec![0, 4]);
+/// ```
+pub struct ShiftAndSearch {
+    pattern: Mapped<ShiftAnd>,
+}
+
+impl ShiftAndSearch {
+    /// Parses and preprocesses the pattern `p`.
+    ///
+    /// The preprocessing runs in O(σ⌈m/w⌉ + c) time, where `m` is the number of
+    /// positions in the pattern, σ is the number of distinct characters in it,
+    /// `w` is 64, the word size, and `c` is the number of characters listed in the
+    /// pattern. For large alphabets the masks only store the characters the pattern
+    /// lists, so they take O(⌈m/w⌉ + c) space.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// A new `ShiftAndSearch` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnterminatedCharacterClass`] or [`Error::EmptyCharacterClass`]
+    /// if the pattern has an unterminated or empty character class, and
+    /// [`Error::DanglingEscape`] if it ends with an escape character.
+    pub fn new(p: &str) -> Result<ShiftAndSearch, Error> {
+        let classes = parse_pattern(p)?;
+        let chars: Vec<char> = classes
+            .iter()
+            .flat_map(|class| match class {
+                CharClass::Any => vec![],
+                CharClass::Set { chars, .. } => chars.clone(),
+            })
+            .collect();
+        let alphabet = Arc::new(Alphabet::new(&chars));
+        let pattern = Mapped::new(StrMappers::new(&alphabet), &classes[..]);
+        Ok(ShiftAndSearch { pattern })
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// in the text `x`, using Shift-And.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern in `x`.
+    pub fn search(&self, x: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        self.pattern.search(x)
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// in the text `x`, using Shift-Or.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The text in which to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern in `x`.
+    pub fn search_shift_or(&self, x: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+        self.pattern.search_as::<ShiftOr>(x)
+    }
+}
+
+impl Algorithm for ShiftAnd {
+    type Input<'a> = &'a [CharClass];
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = Arc<ShiftPattern>;
+
+    fn preprocess<Char: CharacterTrait>(
+        classes: &[CharClass],
+        mapper: &StrMapper<Char>,
+    ) -> Arc<ShiftPattern> {
+        Arc::new(ShiftPattern::new(classes, &mapper.alphabet))
+    }
+
+    fn search<Char: CharacterTrait>(
+        pattern: &Arc<ShiftPattern>,
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if pattern.m == 0 {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(ShiftAndIter {
+            x,
+            pattern: pattern.clone(),
+            j: 0,
+            state: vec![0; pattern.words],
+        })
+    }
+}
+
+impl Algorithm for ShiftOr {
+    type Input<'a> = &'a [CharClass];
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = Arc<ShiftPattern>;
+
+    fn preprocess<Char: CharacterTrait>(
+        classes: &[CharClass],
+        mapper: &StrMapper<Char>,
+    ) -> Arc<ShiftPattern> {
+        ShiftAnd::preprocess(classes, mapper)
+    }
+
+    fn search<Char: CharacterTrait>(
+        pattern: &Arc<ShiftPattern>,
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if pattern.m == 0 {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(ShiftOrIter {
+            x,
+            pattern: pattern.clone(),
+            j: 0,
+            state: vec![!0; pattern.words],
+        })
+    }
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Shift-And algorithm.
+///
+/// The pattern can contain character classes and wildcards, see [`ShiftAndSearch`]
+/// for the syntax. The algorithm keeps a bit for each position `i` in the pattern,
+/// which is set when the first `i + 1` positions of the pattern match the text
+/// ending at the current position, and updates all of them with a shift and a
+/// bitwise and per word. Patterns longer than 64 positions use several words.
+///
+/// The algorithm runs in O(n⌈m/w⌉) time, where `n` is the length of the text,
+/// `m` is the number of positions in the pattern, and `w` is 64, the word size.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is malformed, see [`ShiftAndSearch::new`].
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::shift_and;
+///
+/// let matches: Vec<usize> = shift_and("abracadabra", "a[^b]a").unwrap().collect();
+/// assert_eq!(matches, vec![3, 5]);
+/// let matches: Vec<usize> = shift_and("a.b.c", "\\.b").unwrap().collect();
+/// assert_eq!(matches, vec![1]);
+/// ```
+pub fn shift_and(x: &str, p: &str) -> Result<Box<dyn Iterator<Item = usize> + Send>, Error> {
+    Ok(ShiftAndSearch::new(p)?.search(x))
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Shift-Or algorithm.
+///
+/// This is the Shift-And algorithm with the bits complemented, which saves an
+/// operation per word. See [`shift_and`] for details.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is malformed, see [`ShiftAndSearch::new`].
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::shift_or;
+///
+/// let matches: Vec<usize> = shift_or("abracadabra", "a[bc]").unwrap().collect();
+/// assert_eq!(matches, vec![0, 3, 7]);
+/// ```
+pub fn shift_or(x: &str, p: &str) -> Result<Box<dyn Iterator<Item = usize> + Send>, Error> {
+    Ok(ShiftAndSearch::new(p)?.search_shift_or(x))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    #[test]
+    fn test_parse_pattern() {
+        let classes = parse_pattern("a.[bc][^d]\\.").unwrap();
+        assert_eq!(
+            classes,
+            vec![
+                CharClass::Set {
+                    chars: vec!['a'],
+                    negated: false
+                },
+                CharClass::Any,
+                CharClass::Set {
+                    chars: vec!['b', 'c'],
+                    negated: false
+                },
+                CharClass::Set {
+                    chars: vec!['d'],
+                    negated: true
+                },
+                CharClass::Set {
+                    chars: vec!['.'],
+                    negated: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_errors() {
+        assert_eq!(
+            parse_pattern("a[bc"),
+            Err(Error::UnterminatedCharacterClass { position: 1 })
+        );
+        assert_eq!(
+            parse_pattern("ab[]"),
+            Err(Error::EmptyCharacterClass { position: 2 })
+        );
+        assert_eq!(
+            parse_pattern("a\\"),
+            Err(Error::DanglingEscape { position: 1 })
+        );
+        assert_eq!(
+            parse_pattern("a[b\\"),
+            Err(Error::DanglingEscape { position: 3 })
+        );
+        assert!(shift_and("abc", "[").is_err());
+    }
+
+    #[test]
+    fn test_literal_patterns_match_naive() {
+        let x = "aabaabaaabaabbaabaaab";
+        for p in ["a", "aab", "abba", "bbb", "c"] {
+            let expected: Vec<usize> = naive(x, p).collect();
+            assert_eq!(shift_and(x, p).unwrap().collect::<Vec<_>>(), expected);
+            assert_eq!(shift_or(x, p).unwrap().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_wildcards_match_characters_not_in_pattern() {
+        let result: Vec<usize> = shift_and("axbyb", "[^b]b").unwrap().collect();
+        assert_eq!(result, vec![1, 3]);
+        let result: Vec<usize> = shift_or("axbyb", ".b").unwrap().collect();
+        assert_eq!(result, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_long_patterns() {
+        // A text and a pattern longer than a word, with wildcards at every fifth position
+        let x: String = "acgtt".repeat(40);
+        let p: String = x[5..150]
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i % 5 == 0 { '.' } else { c })
+            .collect();
+        let expected: Vec<usize> = (0..=(x.len() - 145)).filter(|i| i % 5 == 0).collect();
+        assert_eq!(shift_and(&x, &p).unwrap().collect::<Vec<_>>(), expected);
+        assert_eq!(shift_or(&x, &p).unwrap().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(shift_and("abc", "").unwrap().count(), 0);
+        assert_eq!(shift_or("", "a").unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,295 @@
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
+
+/// The Two-Way (Crochemore-Perrin) string matching algorithm, see [`two_way`].
+pub struct TwoWay;
+
+/// Computes the maximal suffix of `p` with respect to the ordering of the characters,
+/// or the reversed ordering if `reversed` is true.
+///
+/// # Returns
+///
+/// The start of the maximal suffix and its period.
+fn maximal_suffix<Char: CharacterTrait>(p: &[Char], reversed: bool) -> (usize, usize) {
+    // The maximal suffix we have found so far starts at `left`, and we are comparing
+    // the candidate starting at `right` against it, `offset` characters in.
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+    while right + offset < p.len() {
+        let a = p[right + offset];
+        let b = p[left + offset];
+        if (a < b && !reversed) || (a > b && reversed) {
+            // The suffix at `right` is smaller, so we skip past the compared part and
+            // the maximal suffix's period grows to cover it.
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            // Advance through the repetition of the current period
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // The suffix at `right` is larger, so it is the new maximal suffix
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+    (left, period)
+}
+
+/// Computes a critical factorization `p = p[..crit] p[crit..]` of `p`.
+///
+/// # Returns
+///
+/// The critical position and the period of `p[crit..]`.
+fn critical_factorization<Char: CharacterTrait>(p: &[Char]) -> (usize, usize) {
+    // The later of the two maximal suffixes gives a critical factorization.
+    let (crit, period) = maximal_suffix(p, false);
+    let (crit_rev, period_rev) = maximal_suffix(p, true);
+    if crit > crit_rev {
+        (crit, period)
+    } else {
+        (crit_rev, period_rev)
+    }
+}
+
+/// A pattern preprocessed for Two-Way search.
+pub struct TwoWayPattern<Char: CharacterTrait> {
+    /// The pattern we are searching for
+    p: Vec<Char>,
+    /// The critical position, where we split the pattern into a left and a right part
+    crit: usize,
+    /// The period of the pattern if it is periodic, otherwise a lower bound on it
+    period: usize,
+    /// Whether the pattern is periodic, in which case we remember how much of the
+    /// left part matched across shifts
+    periodic: bool,
+}
+
+impl<Char: CharacterTrait> TwoWayPattern<Char> {
+    fn new(p: &[Char]) -> TwoWayPattern<Char> {
+        let m = p.len();
+        let (crit, period) = critical_factorization(p);
+        // If the left part is a suffix of the right part's first period, then `period`
+        // is the period of the whole pattern. Otherwise the period is longer than
+        // max(crit, m - crit), and shifting by that is safe.
+        let periodic = crit + period <= m && p[..crit] == p[period..period + crit];
+        let period = if periodic {
+            period
+        } else {
+            crit.max(m - crit) + 1
+        };
+        TwoWayPattern {
+            p: p.to_vec(),
+            crit,
+            period,
+            periodic,
+        }
+    }
+}
+
+struct TwoWayIter<Char: CharacterTrait, X> {
+    /// The string we are searching in
+    x: X,
+    /// The preprocessed pattern we are searching for
+    pattern: Arc<TwoWayPattern<Char>>,
+    /// The current position of the pattern in the text
+    pos: usize,
+    /// The length of the prefix of the pattern we know matches at `pos`
+    /// (only used for periodic patterns)
+    memory: usize,
+}
+
+impl<Char: CharacterTrait, X: AsRef<[Char]>> Iterator for TwoWayIter<Char, X> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let TwoWayIter {
+            x,
+            pattern,
+            pos,
+            memory,
+        } = self;
+        let x = x.as_ref();
+        let TwoWayPattern {
+            p,
+            crit,
+            period,
+            periodic,
+        } = pattern.as_ref();
+        let (n, m) = (x.len(), p.len());
+
+        'outer: while *pos + m <= n {
+            // Match the right part from left to right
+            let start = if *periodic {
+                (*crit).max(*memory)
+            } else {
+                *crit
+            };
+            for i in start..m {
+                if p[i] != x[*pos + i] {
+                    *pos += i - crit + 1;
+                    *memory = 0;
+                    continue 'outer;
+                }
+            }
+
+            // Match the left part from right to left, down to what we already know matches
+            let start = if *periodic { *memory } else { 0 };
+            for i in (start..*crit).rev() {
+                if p[i] != x[*pos + i] {
+                    *pos += period;
+                    if *periodic {
+                        *memory = m - period;
+                    }
+                    continue 'outer;
+                }
+            }
+
+            let j = *pos;
+            *pos += period;
+            if *periodic {
+                *memory = m - period;
+            }
+            return Some(j);
+        }
+        None
+    }
+}
+
+impl ExactAlgorithm for TwoWay {
+    type Pattern<Char: CharacterTrait> = TwoWayPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], _sigma: usize) -> TwoWayPattern<Char> {
+        TwoWayPattern::new(p)
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<TwoWayPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        Box::new(TwoWayIter {
+            x,
+            pattern,
+            pos: 0,
+            memory: 0,
+        })
+    }
+}
+
+/// A pattern preprocessed for the Two-Way string matching algorithm.
+///
+/// The preprocessing only computes a critical factorization of the pattern and
+/// its period, so apart from the mapped pattern it uses constant space. Each
+/// search maps the text to the pattern's alphabet, which takes O(n) space for a
+/// text of length `n`, and the search itself only uses constant working space.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{TwoWaySearch, Searcher};
+///
+/// let search = TwoWaySearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type TwoWaySearch = Preprocessed<TwoWay>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Two-Way algorithm of Crochemore and Perrin.
+///
+/// The pattern is split at a critical position, computed from the maximal suffixes
+/// of the pattern under the character ordering and its reverse. Each attempt first
+/// matches the right part from left to right and then the left part from right to
+/// left, and the period of the pattern determines the shift after a mismatch in the
+/// left part or a match.
+///
+/// The algorithm runs in O(n + m) time, where `n` is the length of the text and
+/// `m` is the length of the pattern. It uses O(n) space for the text mapped to the
+/// pattern's alphabet, and only constant working space on top of that.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::two_way;
+///
+/// let matches: Vec<usize> = two_way("abracadabra", "abra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = two_way("aaaaa", "aa").collect();
+/// assert_eq!(matches, vec![0, 1, 2, 3]);
+/// ```
+pub fn two_way(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    TwoWaySearch::new(p).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_maximal_suffix() {
+        // With a < b < c the maximal suffix of "abcabc" is "cabc", and with the
+        // reversed ordering it is the whole string. Both have period 3.
+        let p: Vec<u8> = vec![1, 2, 3, 1, 2, 3];
+        assert_eq!(maximal_suffix(&p, false), (2, 3));
+        assert_eq!(maximal_suffix(&p, true), (0, 3));
+    }
+
+    /// The smallest `r` such that the characters `r` apart agree across position `c`.
+    fn local_period(p: &[u8], c: usize) -> usize {
+        (1..=p.len())
+            .find(|&r| (c.saturating_sub(r)..c).all(|i| i + r >= p.len() || p[i] == p[i + r]))
+            .unwrap()
+    }
+
+    /// The smallest period of `p`.
+    fn period(p: &[u8]) -> usize {
+        (1..=p.len())
+            .find(|&r| (0..p.len() - r).all(|i| p[i] == p[i + r]))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_critical_factorization() {
+        // The local period at a critical position is the period of the pattern
+        let mut patterns: Vec<Vec<u8>> = vec![vec![]];
+        for _ in 0..10 {
+            patterns = patterns
+                .iter()
+                .flat_map(|p| (1..=3).map(move |a| [p.as_slice(), &[a]].concat()))
+                .collect();
+            for p in &patterns {
+                let (crit, _) = critical_factorization(p);
+                assert_eq!(local_period(p, crit), period(p), "p = {p:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use super::preprocessed::{Algorithm, Mapped};
+use crate::utils::{Alphabet, CharacterTrait, Str, StrMapper, StrMappers};
+use std::sync::Arc;
+
+/// Naive search with wildcards, see [`wildcard_naive`].
+struct WildcardNaive;
+
+/// Search with wildcards using number-theoretic transforms, see [`wildcard`].
+struct WildcardNTT;
+
+/// The prime 2^64 - 2^32 + 1, which we do the number-theoretic transforms modulo.
+const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+/// A generator of the multiplicative group modulo `MODULUS`.
+const GENERATOR: u64 = 7;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 + b as u128) % MODULUS as u128) as u64
+}
+
+fn sub_mod(a: u64, b: u64) -> u64 {
+    add_mod(a, MODULUS - b)
+}
+
+fn pow_mod(mut a: u64, mut e: u64) -> u64 {
+    let mut result = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a);
+        }
+        a = mul_mod(a, a);
+        e >>= 1;
+    }
+    result
+}
+
+/// Computes the number-theoretic transform of `a` in place, or the inverse transform
+/// if `inverse` is true. The length of `a` must be a power of two.
+fn ntt(a: &mut [u64], inverse: bool) {
+    let n = a.len();
+    // Permute the elements into bit-reversed order
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    // Combine transforms of doubling length
+    let mut len = 2;
+    while len <= n {
+        let mut w = pow_mod(GENERATOR, (MODULUS - 1) / len as u64);
+        if inverse {
+            w = pow_mod(w, MODULUS - 2);
+        }
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = 1;
+            for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = mul_mod(*v, wk);
+                *v = sub_mod(*u, t);
+                *u = add_mod(*u, t);
+                wk = mul_mod(wk, w);
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        let n_inv = pow_mod(n as u64, MODULUS - 2);
+        for x in a.iter_mut() {
+            *x = mul_mod(*x, n_inv);
+        }
+    }
+}
+
+/// Maps the text `x` to the alphabet of the pattern. The wildcard is mapped to the
+/// sentinel zero, and characters that are not in the pattern are mapped to one past
+/// the largest rank, so they only match wildcards.
+fn map_text<Char: CharacterTrait>(x: &str, wildcard: char, mapper: &StrMapper<Char>) -> Str<Char> {
+    let alphabet = &mapper.alphabet;
+    // There is room for one extra character in Char, so this cannot fail.
+    let other = Char::try_from(alphabet.len() + 1).unwrap();
+    let x: Vec<Char> = x
+        .chars()
+        .map(|c| match alphabet.index(c) {
+            _ if c == wildcard => Char::try_from(0).unwrap(),
+            Some(a) => Char::try_from(a).unwrap(),
+            None => other,
+        })
+        .collect();
+    Str::new(x, alphabet)
+}
+
+/// Checks if the pattern matches the text at position `i`, where zero is the wildcard.
+fn matches_at<Char: CharacterTrait>(x: &Str<Char>, p: &Str<Char>, i: usize) -> bool {
+    x[i..i + p.len()]
+        .iter()
+        .zip(p.iter())
+        .all(|(&a, &b)| a.to_usize() == 0 || b.to_usize() == 0 || a == b)
+}
+
+/// Builds the alphabet of the pattern without the wildcard, so the wildcard
+/// is mapped to the sentinel.
+fn wildcard_mapper(p: &str, wildcard: char) -> StrMappers {
+    let chars: Vec<char> = p.chars().filter(|&c| c != wildcard).collect();
+    StrMappers::new(&Arc::new(Alphabet::new(&chars)))
+}
+
+struct WildcardNaiveIter<Char: CharacterTrait> {
+    x: Str<Char>,
+    p: Str<Char>,
+    i: usize,
+}
+
+impl<Char: CharacterTrait> Iterator for WildcardNaiveIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let WildcardNaiveIter { x, p, i } = self;
+        while *i + p.len() <= x.len() {
+            let j = *i;
+            *i += 1;
+            if matches_at(x, p, j) {
+                return Some(j);
+            }
+        }
+        None
+    }
+}
+
+impl Algorithm for WildcardNaive {
+    /// The pattern and the wildcard
+    type Input<'a> = (&'a str, char);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = (Str<Char>, char);
+
+    fn preprocess<Char: CharacterTrait>(
+        (p, wildcard): (&str, char),
+        mapper: &StrMapper<Char>,
+    ) -> (Str<Char>, char) {
+        // The wildcard is the only character not in the alphabet, and it maps to the sentinel.
+        (mapper.map_str_lossy(p), wildcard)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, _): &(Str<Char>, char),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if x.len() < p.len() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(WildcardNaiveIter {
+            x,
+            p: p.clone(),
+            i: 0,
+        })
+    }
+
+    fn map_text<Char: CharacterTrait>(
+        (_, wildcard): &(Str<Char>, char),
+        mapper: &StrMapper<Char>,
+        x: &str,
+    ) -> Str<Char> {
+        map_text(x, *wildcard, mapper)
+    }
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x`, where the character `wildcard` in either string matches
+/// any character, using the naive algorithm.
+///
+/// The algorithm runs in worst time O((n - m + 1) * m), where `n` is the length
+/// of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `wildcard` - The don't care character.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::wildcard_naive;
+///
+/// let matches: Vec<usize> = wildcard_naive("abracadabra", "a*a", '*').collect();
+/// assert_eq!(matches, vec![3, 5]);
+/// let matches: Vec<usize> = wildcard_naive("ab*acad*bra", "abr", '*').collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn wildcard_naive(x: &str, p: &str, wildcard: char) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    Mapped::<WildcardNaive>::new(wildcard_mapper(p, wildcard), (p, wildcard)).search(x)
+}
+
+/// Searches the text in blocks, with the correlations for each block computed
+/// with number-theoretic transforms.
+struct WildcardIter<Char: CharacterTrait> {
+    /// The text we are searching in
+    x: Str<Char>,
+    /// The pattern we are searching for
+    p: Str<Char>,
+    /// The transform size, a power of two at least twice the pattern length
+    len: usize,
+    /// The transforms of the reversed pattern's powers p, p^2 and p^3
+    p_transforms: [Vec<u64>; 3],
+    /// Whether the sums can wrap around the modulus, so hits must be verified
+    verify: bool,
+    /// The start of the next block of the text
+    block: usize,
+    /// The hits in the current block that we haven't reported yet
+    hits: std::vec::IntoIter<usize>,
+}
+
+impl<Char: CharacterTrait> WildcardIter<Char> {
+    fn new(x: Str<Char>, p: Str<Char>, sigma: usize) -> WildcardIter<Char> {
+        let m = p.len();
+        let len = (2 * m).next_power_of_two();
+        let p_transforms = [1, 2, 3].map(|e| {
+            let mut a = vec![0u64; len];
+            for (i, c) in p.iter().rev().enumerate() {
+                a[i] = pow_mod(c.to_usize() as u64, e);
+            }
+            ntt(&mut a, false);
+            a
+        });
+        // Each term of the sum is at most (σ + 1)^4, so if m of them fit below the
+        // modulus the sums are exact.
+        let max_term = (sigma as u64 + 1).saturating_pow(4);
+        let verify = max_term.saturating_mul(m as u64) >= MODULUS;
+        WildcardIter {
+            x,
+            p,
+            len,
+            p_transforms,
+            verify,
+            block: 0,
+            hits: Vec::new().into_iter(),
+        }
+    }
+
+    /// Finds the hits for the windows starting in the block at `self.block`.
+    fn search_block(&mut self) -> Vec<usize> {
+        let WildcardIter {
+            x,
+            p,
+            len,
+            p_transforms,
+            verify,
+            block,
+            ..
+        } = self;
+        let (n, m, len) = (x.len(), p.len(), *len);
+        // The windows starting at block..block + len - m + 1 all fit in the
+        // transform, and the wrap-around of the cyclic correlation only affects
+        // the first m - 1 entries, which we don't use.
+        let end = (*block + len).min(n);
+        let mut x_transforms = [1, 2, 3].map(|e| {
+            let mut a = vec![0u64; len];
+            for (i, c) in x[*block..end].iter().enumerate() {
+                a[i] = pow_mod(c.to_usize() as u64, e);
+            }
+            ntt(&mut a, false);
+            a
+        });
+        // sum_j p_j x_{i+j} (p_j - x_{i+j})^2 = sum p^3 x - 2 sum p^2 x^2 + sum p x^3,
+        // which is zero exactly when every pair of characters is equal or one of
+        // them is a wildcard (zero).
+        let [x1, x2, x3] = &mut x_transforms;
+        let [p1, p2, p3] = p_transforms;
+        for k in 0..len {
+            let a = mul_mod(p3[k], x1[k]);
+            let b = mul_mod(p2[k], x2[k]);
+            let c = mul_mod(p1[k], x3[k]);
+            x1[k] = sub_mod(add_mod(a, c), add_mod(b, b));
+        }
+        ntt(x1, true);
+
+        let windows = (len - m + 1).min(n - m + 1 - *block);
+        let hits: Vec<usize> = (0..windows)
+            .filter(|&i| x1[m - 1 + i] == 0)
+            .map(|i| *block + i)
+            .filter(|&i| !*verify || matches_at(x, p, i))
+            .collect();
+        *block += windows;
+        hits
+    }
+}
+
+impl<Char: CharacterTrait> Iterator for WildcardIter<Char> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if let Some(i) = self.hits.next() {
+                return Some(i);
+            }
+            if self.block + self.p.len() > self.x.len() {
+                return None;
+            }
+            self.hits = self.search_block().into_iter();
+        }
+    }
+}
+
+impl Algorithm for WildcardNTT {
+    /// The pattern and the wildcard
+    type Input<'a> = (&'a str, char);
+    type Item = usize;
+    type Pattern<Char: CharacterTrait> = (Str<Char>, char);
+
+    fn preprocess<Char: CharacterTrait>(
+        input: (&str, char),
+        mapper: &StrMapper<Char>,
+    ) -> (Str<Char>, char) {
+        WildcardNaive::preprocess(input, mapper)
+    }
+
+    fn search<Char: CharacterTrait>(
+        (p, _): &(Str<Char>, char),
+        x: Str<Char>,
+    ) -> Box<dyn Iterator<Item = usize> + Send> {
+        if x.len() < p.len() {
+            return Box::new(std::iter::empty());
+        }
+        let sigma = p.alphabet.len() + 1;
+        Box::new(WildcardIter::new(x, p.clone(), sigma))
+    }
+
+    fn map_text<Char: CharacterTrait>(
+        pattern: &(Str<Char>, char),
+        mapper: &StrMapper<Char>,
+        x: &str,
+    ) -> Str<Char> {
+        WildcardNaive::map_text(pattern, mapper, x)
+    }
+}
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x`, where the character `wildcard` in either string matches
+/// any character.
+///
+/// The wildcard is mapped to zero and the other characters to positive ranks.
+/// Then the pattern matches at position `i` exactly when the sum over `j` of
+/// `p[j] x[i + j] (p[j] - x[i + j])^2` is zero, and this sum expands into three
+/// correlations that we compute with number-theoretic transforms. The text is
+/// processed in blocks of length O(m), so each block takes O(m log m) time.
+///
+/// The algorithm runs in O(n log m) time, where `n` is the length of the text
+/// and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+/// * `wildcard` - The don't care character.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::wildcard;
+///
+/// let matches: Vec<usize> = wildcard("abracadabra", "a*a", '*').collect();
+/// assert_eq!(matches, vec![3, 5]);
+/// let matches: Vec<usize> = wildcard("ab*acad*bra", "abr", '*').collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn wildcard(x: &str, p: &str, wildcard: char) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    Mapped::<WildcardNTT>::new(wildcard_mapper(p, wildcard), (p, wildcard)).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::naive;
+
+    fn all_strings(letters: &[char], n: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        for _ in 0..n {
+            strings = strings
+                .iter()
+                .flat_map(|s| letters.iter().map(move |c| format!("{s}{c}")))
+                .collect();
+        }
+        strings
+    }
+
+    #[test]
+    fn test_ntt_convolution() {
+        let mut a = vec![1, 2, 3, 0, 0, 0, 0, 0];
+        let mut b = vec![4, 5, 0, 0, 0, 0, 0, 0];
+        ntt(&mut a, false);
+        ntt(&mut b, false);
+        let mut c: Vec<u64> = a.iter().zip(&b).map(|(&a, &b)| mul_mod(a, b)).collect();
+        ntt(&mut c, true);
+        assert_eq!(c, vec![4, 13, 22, 15, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_without_wildcards_matches_naive() {
+        let x = "aabaabaaabaabbaabaaab";
+        for p in ["a", "aab", "abba", "bbb", "c"] {
+            let expected: Vec<usize> = naive(x, p).collect();
+            assert_eq!(wildcard_naive(x, p, '*').collect::<Vec<_>>(), expected);
+            assert_eq!(wildcard(x, p, '*').collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_wildcard_matches_naive() {
+        // Wildcards in both strings, and a character in the text that is not in
+        // any pattern
+        for x in all_strings(&['a', 'b', 'c', '*'], 5) {
+            for m in 1..=3 {
+                for p in all_strings(&['a', 'b', '*'], m) {
+                    let expected: Vec<usize> = wildcard_naive(&x, &p, '*').collect();
+                    let result: Vec<usize> = wildcard(&x, &p, '*').collect();
+                    assert_eq!(result, expected, "x = {x}, p = {p}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiple_blocks() {
+        let x: String = "acgt*acgtaacgtt".repeat(30);
+        for p in ["acg", "a*gt", "t*a*g", "acgt*acgtaacgtt*cg"] {
+            let expected: Vec<usize> = wildcard_naive(&x, p, '*').collect();
+            let result: Vec<usize> = wildcard(&x, p, '*').collect();
+            assert!(!expected.is_empty());
+            assert_eq!(result, expected, "p = {p}");
+        }
+    }
+
+    #[test]
+    fn test_only_wildcards() {
+        let result: Vec<usize> = wildcard("abcd", "**", '*').collect();
+        assert_eq!(result, vec![0, 1, 2]);
+    }
+}
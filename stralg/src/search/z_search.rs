@@ -0,0 +1,120 @@
+use super::{ExactAlgorithm, Preprocessed, Searcher};
+use crate::patterns::z_array_of;
+use crate::utils::CharacterTrait;
+use std::sync::Arc;
+
+/// Z-array based string matching, see [`z_search`].
+pub struct Z;
+
+/// A pattern prepared for Z-array based search.
+pub struct ZPattern<Char: CharacterTrait> {
+    /// The pattern we are searching for
+    p: Vec<Char>,
+}
+
+impl ExactAlgorithm for Z {
+    type Pattern<Char: CharacterTrait> = ZPattern<Char>;
+
+    fn preprocess<Char: CharacterTrait>(p: &[Char], _sigma: usize) -> ZPattern<Char> {
+        ZPattern { p: p.to_vec() }
+    }
+
+    fn search<'a, Char, X>(
+        pattern: Arc<ZPattern<Char>>,
+        x: X,
+    ) -> Box<dyn Iterator<Item = usize> + Send + 'a>
+    where
+        Char: CharacterTrait,
+        X: AsRef<[Char]> + Send + 'a,
+    {
+        let (p, x) = (&pattern.p, x.as_ref());
+        let m = p.len();
+
+        // Build p $ x, using the sentinel zero as the separator $.
+        let sentinel = Char::try_from(0).unwrap();
+        let mut px = Vec::with_capacity(m + 1 + x.len());
+        px.extend_from_slice(p);
+        px.push(sentinel);
+        px.extend_from_slice(x);
+
+        // Since characters in x that are not in p are also mapped to the sentinel, a
+        // Z-value can run past the separator, so we test for at least m rather than m.
+        let z = z_array_of(&px);
+        Box::new(
+            z.into_iter()
+                .enumerate()
+                .skip(m + 1)
+                .filter(move |&(_, len)| len >= m)
+                .map(move |(i, _)| i - m - 1),
+        )
+    }
+}
+
+/// A pattern prepared for Z-array based string matching.
+///
+/// The pattern's alphabet is only built once, when the `ZSearch` is created, and
+/// can then be reused for searching any number of texts.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::search::{ZSearch, Searcher};
+///
+/// let search = ZSearch::new("abr");
+/// let matches: Vec<usize> = search.search("abracadabra").collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// let matches: Vec<usize> = search.search("cobra abra").collect();
+/// assert_eq!(matches, vec![6]);
+/// ```
+pub type ZSearch = Preprocessed<Z>;
+
+/// Returns an iterator over the starting indices of occurrences of the pattern
+/// `p` in the text `x` using the Z-array.
+///
+/// The algorithm computes the Z-array of the string `p$x`, where `$` is the
+/// alphabet's sentinel, and reports the positions in `x` where the Z-value is at
+/// least the length of `p`, i.e., where the suffix of `p$x` starts with `p`.
+///
+/// The algorithm runs in O(n + m) time and uses O(n + m) extra space, where `n`
+/// is the length of the text and `m` is the length of the pattern.
+///
+/// # Arguments
+///
+/// * `x` - The text in which to search for the pattern.
+/// * `p` - The pattern to search for.
+///
+/// # Returns
+///
+/// An iterator over the starting indices of occurrences of the pattern `p` in
+/// the text `x`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::z_search;
+///
+/// let text = "abracadabra";
+/// let pattern = "abr";
+/// let matches: Vec<usize> = z_search(text, pattern).collect();
+/// assert_eq!(matches, vec![0, 7]);
+/// ```
+pub fn z_search(x: &str, p: &str) -> Box<dyn Iterator<Item = usize> + Send> {
+    if x.is_empty() || p.is_empty() {
+        return Box::new(std::iter::empty());
+    }
+    ZSearch::new(p).search(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_z_search_with_characters_not_in_p() {
+        // The x's are mapped to the sentinel, just like the separator
+        let result: Vec<usize> = z_search("xaxxa", "a").collect();
+        assert_eq!(result, vec![1, 4]);
+        let result: Vec<usize> = z_search("abxabx", "ab").collect();
+        assert_eq!(result, vec![0, 3]);
+    }
+}
@@ -0,0 +1,122 @@
+use super::{kasai, sais};
+use crate::utils::{AsStrSlice, CharacterTrait};
+
+/// Longest common extension queries over a string.
+///
+/// An LCE query asks for the length of the longest common prefix of two suffixes
+/// of the string. This is the minimum over the LCP array between the ranks of the
+/// two suffixes, so we answer it with a range minimum query over the LCP array,
+/// using a sparse table with the minimum of every interval of length `2^k`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::LCE;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("abracadabra"));
+/// let x: Str<u8> = Str::from_str("abracadabra", &alphabet).unwrap();
+/// let lce = LCE::new(&x);
+/// assert_eq!(lce.lce(0, 7), 4); // abra
+/// assert_eq!(lce.lce(3, 5), 1); // a
+/// assert_eq!(lce.lce(1, 2), 0);
+/// ```
+pub struct LCE {
+    /// The rank of each suffix in the suffix array
+    rank: Vec<usize>,
+    /// The sparse table, where `table[k][i]` is the minimum of `lcp[i..i + 2^k]`
+    table: Vec<Vec<usize>>,
+}
+
+impl LCE {
+    /// Preprocesses the string `x` for LCE queries.
+    ///
+    /// The preprocessing runs in O(n log n) time and space, where `n` is the length of `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to answer queries over.
+    ///
+    /// # Returns
+    ///
+    /// A new `LCE` instance.
+    pub fn new<Char, S>(x: &S) -> LCE
+    where
+        Char: CharacterTrait,
+        S: AsStrSlice<Char>,
+    {
+        let sa = sais(x);
+        let lcp = kasai(x, &sa);
+        let mut rank = vec![0; sa.len()];
+        for (r, &i) in sa.iter().enumerate() {
+            rank[i] = r;
+        }
+
+        let mut table = vec![lcp];
+        let mut width = 1;
+        while 2 * width <= sa.len() {
+            let prev = table.last().unwrap();
+            let row = (0..(prev.len() - width))
+                .map(|i| prev[i].min(prev[i + width]))
+                .collect();
+            table.push(row);
+            width *= 2;
+        }
+        LCE { rank, table }
+    }
+
+    /// Returns the length of the longest common prefix of the suffixes starting at
+    /// `i` and `j`.
+    ///
+    /// The query runs in O(1) time.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The start of the first suffix.
+    /// * `j` - The start of the second suffix.
+    ///
+    /// # Returns
+    ///
+    /// The length of the longest common prefix of `x[i..]` and `x[j..]`.
+    pub fn lce(&self, i: usize, j: usize) -> usize {
+        if i == j {
+            return self.rank.len() - 1 - i; // The sentinel suffix is at index n
+        }
+        let (a, b) = (
+            self.rank[i].min(self.rank[j]),
+            self.rank[i].max(self.rank[j]),
+        );
+        // The LCP of the suffixes is the minimum of lcp[a + 1..=b]
+        let len = b - a;
+        let k = len.ilog2() as usize;
+        self.table[k][a + 1].min(self.table[k][b + 1 - (1 << k)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::Str;
+    use crate::Alphabet;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_lce_matches_naive() {
+        for x in ["a", "aaaaaaa", "abracadabra", "aabaabaaabaabbaabaaab"] {
+            let alphabet = Arc::new(Alphabet::from_str(x));
+            let x: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+            let lce = LCE::new(&x);
+            for i in 0..=x.len() {
+                for j in 0..=x.len() {
+                    let expected = x[i..]
+                        .iter()
+                        .zip(x[j..].iter())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    assert_eq!(lce.lce(i, j), expected, "i = {i}, j = {j}");
+                }
+            }
+        }
+    }
+}
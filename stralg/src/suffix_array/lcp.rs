@@ -0,0 +1,409 @@
+use super::SuffixArray;
+use crate::utils::{AsStrSlice, CharacterTrait, Str};
+
+/// Computes the length of the longest common prefix of `x[i..]` and `x[j..]`,
+/// skipping the first `k` characters that we already know match.
+fn extend_match<Char>(x: &[Char], i: usize, j: usize, mut k: usize) -> usize
+where
+    Char: CharacterTrait,
+{
+    while i + k < x.len() && j + k < x.len() && x[i + k] == x[j + k] {
+        k += 1;
+    }
+    k
+}
+
+/// Computes the LCP array of the string `x` from its suffix array `sa` using
+/// Kasai's algorithm.
+///
+/// The value at index `i` of the LCP array is the length of the longest common
+/// prefix of the suffixes at `sa[i - 1]` and `sa[i]`, and the value at index zero
+/// is zero. The suffix array must be the suffix array of `x` followed by the sentinel,
+/// as computed by [`sais`](super::sais).
+///
+/// The algorithm runs in O(n) time and uses an extra array of `n + 1` integers
+/// for the inverse suffix array, where `n` is the length of `x`.
+///
+/// # Arguments
+///
+/// * `x` - The string.
+/// * `sa` - The suffix array of `x`.
+///
+/// # Returns
+///
+/// The LCP array of `x`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::{kasai, sais};
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let sa = sais(&x);
+/// assert_eq!(kasai(&x, &sa), vec![0, 0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]);
+/// ```
+pub fn kasai<Char, S>(x: &S, sa: &[usize]) -> Vec<usize>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let x = x.as_str_slice();
+    let mut rank = vec![0; sa.len()];
+    for (r, &i) in sa.iter().enumerate() {
+        rank[i] = r;
+    }
+
+    // Going through the suffixes in text order, the LCP with the preceding suffix
+    // in the suffix array drops by at most one from one suffix to the next.
+    let mut lcp = vec![0; sa.len()];
+    let mut k: usize = 0;
+    for (i, &r) in rank.iter().enumerate() {
+        if r == 0 {
+            k = 0;
+            continue;
+        }
+        k = extend_match(x.as_slice(), i, sa[r - 1], k);
+        lcp[r] = k;
+        k = k.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Computes the LCP array of the string `x` from its suffix array `sa` through
+/// the permuted LCP (PLCP) array, using the Φ algorithm.
+///
+/// The result is the same as for [`kasai`], but instead of the inverse suffix array
+/// we compute Φ, which maps each suffix to the suffix that precedes it in the suffix
+/// array, and then overwrite Φ with the PLCP array, the LCP values in text order.
+/// Unlike Kasai's algorithm, this accesses the suffix array and the string in order
+/// when computing PLCP, so it is more cache friendly as well.
+///
+/// The algorithm runs in O(n) time, where `n` is the length of `x`.
+///
+/// # Arguments
+///
+/// * `x` - The string.
+/// * `sa` - The suffix array of `x`.
+///
+/// # Returns
+///
+/// The LCP array of `x`.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::{phi_lcp, sais};
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let sa = sais(&x);
+/// assert_eq!(phi_lcp(&x, &sa), vec![0, 0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]);
+/// ```
+pub fn phi_lcp<Char, S>(x: &S, sa: &[usize]) -> Vec<usize>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let x = x.as_str_slice();
+    // The first suffix in the suffix array has no predecessor, and we mark it
+    // with a value that can't be a suffix index.
+    const NO_PREDECESSOR: usize = usize::MAX;
+    let mut plcp = vec![NO_PREDECESSOR; sa.len()];
+    for r in 1..sa.len() {
+        plcp[sa[r]] = sa[r - 1];
+    }
+
+    // Replace Φ by PLCP in place; we only read phi[i] before we write plcp[i].
+    let mut k: usize = 0;
+    for (i, v) in plcp.iter_mut().enumerate() {
+        let j = *v;
+        if j == NO_PREDECESSOR {
+            k = 0;
+            *v = 0;
+            continue;
+        }
+        k = extend_match(x.as_slice(), i, j, k);
+        *v = k;
+        k = k.saturating_sub(1);
+    }
+
+    sa.iter().map(|&i| plcp[i]).collect()
+}
+
+/// Fills in the LCP values for the binary search intervals below `(left, right)`,
+/// and returns the length of the longest common prefix of the suffixes at the two ends.
+fn build_lcp_lr(
+    lcp: &[usize],
+    left: usize,
+    right: usize,
+    llcp: &mut [usize],
+    rlcp: &mut [usize],
+) -> usize {
+    if right - left <= 1 {
+        return lcp[right];
+    }
+    let mid = left + (right - left) / 2;
+    llcp[mid] = build_lcp_lr(lcp, left, mid, llcp, rlcp);
+    rlcp[mid] = build_lcp_lr(lcp, mid, right, llcp, rlcp);
+    llcp[mid].min(rlcp[mid])
+}
+
+/// A suffix array with its LCP array and the LCP-LR tables for fast searching.
+///
+/// With the LCP-LR tables, we know the longest common prefix of the suffix in the
+/// middle of each binary search interval and the suffixes at the ends, which lets
+/// the search skip characters it has already compared, so a search takes
+/// O(m + log n) time instead of O(m log n).
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::LCPSuffixArray;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let sa = LCPSuffixArray::new(x);
+///
+/// let mut matches: Vec<usize> = sa.search("ssi").collect();
+/// matches.sort();
+/// assert_eq!(matches, vec![2, 5]);
+/// ```
+pub struct LCPSuffixArray<Char: CharacterTrait> {
+    /// The suffix array
+    sa: SuffixArray<Char>,
+    /// The LCP array
+    lcp: Vec<usize>,
+    /// The longest common prefix of the middle suffix and the left suffix of each search interval
+    llcp: Vec<usize>,
+    /// The longest common prefix of the middle suffix and the right suffix of each search interval
+    rlcp: Vec<usize>,
+}
+
+impl<Char: CharacterTrait> LCPSuffixArray<Char> {
+    /// Builds the suffix array, the LCP array, and the LCP-LR tables for the string `x`.
+    ///
+    /// The construction runs in O(n) time, where `n` is the length of `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to build the suffix array over.
+    ///
+    /// # Returns
+    ///
+    /// A new `LCPSuffixArray` instance.
+    pub fn new(x: Str<Char>) -> LCPSuffixArray<Char> {
+        let sa = SuffixArray::new(x);
+        let lcp = kasai(sa.text(), sa.sa());
+        let mut llcp = vec![0; lcp.len()];
+        let mut rlcp = vec![0; lcp.len()];
+        if lcp.len() > 1 {
+            build_lcp_lr(&lcp, 0, lcp.len() - 1, &mut llcp, &mut rlcp);
+        }
+        LCPSuffixArray {
+            sa,
+            lcp,
+            llcp,
+            rlcp,
+        }
+    }
+
+    /// Returns the string the suffix array is built over.
+    pub fn text(&self) -> &Str<Char> {
+        self.sa.text()
+    }
+
+    /// Returns the suffix array as a slice.
+    pub fn sa(&self) -> &[usize] {
+        self.sa.sa()
+    }
+
+    /// Returns the LCP array as a slice.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Compares the suffix at index `r` in the suffix array against `p`, starting at
+    /// offset `k`, and returns the length of their longest common prefix (capped at
+    /// the length of `p`) and whether the suffix's first `p.len()` characters come
+    /// after `p` (or equal it, if `inclusive`).
+    fn compare(&self, r: usize, p: &[Char], k: usize, inclusive: bool) -> (usize, bool) {
+        let x = self.text();
+        let i = self.sa()[r];
+        let mut k = k;
+        while k < p.len() && i + k < x.len() && x[i + k] == p[k] {
+            k += 1;
+        }
+        let after = if k == p.len() {
+            inclusive
+        } else {
+            i + k < x.len() && x[i + k] > p[k]
+        };
+        (k, after)
+    }
+
+    /// Finds the first index in the suffix array whose suffix comes after `p`, when
+    /// we only look at the first `p.len()` characters. If `inclusive`, suffixes
+    /// starting with `p` count as coming after it.
+    fn bound(&self, p: &[Char], inclusive: bool) -> usize {
+        let n = self.sa().len();
+        let (mut r, after) = self.compare(n - 1, p, 0, inclusive);
+        if !after {
+            return n;
+        }
+
+        // Invariant: the suffix at left is before p and the suffix at right is after,
+        // with l and r the lengths of their longest common prefixes with p. The
+        // sentinel suffix at index zero is before any non-empty pattern.
+        let (mut left, mut right) = (0, n - 1);
+        let mut l = 0;
+        while right - left > 1 {
+            let mid = left + (right - left) / 2;
+            let (k, after) = if l >= r {
+                let llcp = self.llcp[mid];
+                match llcp.cmp(&l) {
+                    // mid agrees with left past the point where left and p differ
+                    std::cmp::Ordering::Greater => (l, false),
+                    // mid differs from left where left still agrees with p
+                    std::cmp::Ordering::Less => (llcp, true),
+                    std::cmp::Ordering::Equal => self.compare(mid, p, l, inclusive),
+                }
+            } else {
+                let rlcp = self.rlcp[mid];
+                match rlcp.cmp(&r) {
+                    std::cmp::Ordering::Greater => (r, true),
+                    std::cmp::Ordering::Less => (rlcp, false),
+                    std::cmp::Ordering::Equal => self.compare(mid, p, r, inclusive),
+                }
+            };
+            if after {
+                right = mid;
+                r = k;
+            } else {
+                left = mid;
+                l = k;
+            }
+        }
+        right
+    }
+
+    /// Returns the interval in the suffix array of the suffixes that have `p` as a prefix.
+    ///
+    /// The search runs in O(m + log n) time, where `m` is the length of the pattern
+    /// and `n` is the length of the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for. It must use the same alphabet as the string.
+    ///
+    /// # Returns
+    ///
+    /// The range of indices into the suffix array whose suffixes start with `p`.
+    pub fn interval<S: AsStrSlice<Char>>(&self, p: &S) -> std::ops::Range<usize> {
+        let p = p.as_str_slice();
+        if p.is_empty() {
+            return 0..self.sa().len();
+        }
+        self.bound(p.as_slice(), true)..self.bound(p.as_slice(), false)
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// `p` in the string.
+    ///
+    /// The occurrences are reported in the order their suffixes have in the suffix
+    /// array, not in the order they have in the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern `p`.
+    pub fn search(&self, p: &str) -> Box<dyn Iterator<Item = usize> + Send + '_> {
+        if p.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        let p = match self.text().translate_to_this_alphabet(p) {
+            Ok(p) => p,
+            Err(_) => return Box::new(std::iter::empty()),
+        };
+        Box::new(self.sa()[self.interval(&p)].iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::suffix_array::sais;
+    use crate::Alphabet;
+    use std::sync::Arc;
+
+    fn naive_lcp(x: &Str<u8>, sa: &[usize]) -> Vec<usize> {
+        let mut lcp = vec![0; sa.len()];
+        for r in 1..sa.len() {
+            lcp[r] = extend_match(&x[..], sa[r - 1], sa[r], 0);
+        }
+        lcp
+    }
+
+    #[test]
+    fn test_lcp_arrays() {
+        for x in [
+            "",
+            "a",
+            "aaaaaa",
+            "abracadabra",
+            "mississippi",
+            "aabaabaaabaab",
+        ] {
+            let alphabet = Arc::new(Alphabet::from_str(x));
+            let x: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+            let sa = sais(&x);
+            let expected = naive_lcp(&x, &sa);
+            assert_eq!(kasai(&x, &sa), expected);
+            assert_eq!(phi_lcp(&x, &sa), expected);
+        }
+    }
+
+    #[test]
+    fn test_lcp_suffix_array_search() {
+        let x = "aabaabaaabaabbaabaaab";
+        let alphabet = Arc::new(Alphabet::from_str(x));
+        let x: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+        let sa = SuffixArray::new(x.clone());
+        let lcp_sa = LCPSuffixArray::new(x);
+        for m in 1..=5 {
+            for bits in 0..(1u32 << m) {
+                let p: String = (0..m)
+                    .map(|k| if (bits >> k) & 1 == 0 { 'a' } else { 'b' })
+                    .collect();
+                let p = lcp_sa.text().translate_to_this_alphabet(&p).unwrap();
+                assert_eq!(lcp_sa.interval(&p), sa.interval(&p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lcp_suffix_array_search_not_in_alphabet() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let sa = LCPSuffixArray::new(Str::<u8>::from_str("abcabc", &alphabet).unwrap());
+        assert_eq!(sa.search("abx").count(), 0);
+        assert_eq!(sa.search("").count(), 0);
+        assert_eq!(sa.search("abcabcabc").count(), 0);
+    }
+
+    #[test]
+    fn test_lcp_suffix_array_single_character() {
+        let alphabet = Arc::new(Alphabet::from_str("a"));
+        let sa = LCPSuffixArray::new(Str::<u8>::from_str("a", &alphabet).unwrap());
+        let matches: Vec<usize> = sa.search("a").collect();
+        assert_eq!(matches, vec![0]);
+    }
+}
@@ -0,0 +1,188 @@
+pub mod lce;
+pub mod lcp;
+pub mod sais;
+
+pub use lce::LCE;
+pub use lcp::{kasai, phi_lcp, LCPSuffixArray};
+pub use sais::sais;
+
+use crate::utils::{AsStrSlice, CharacterTrait, Str};
+use std::cmp::Ordering;
+
+/// A suffix array over a string, for searching the string repeatedly.
+///
+/// The suffix array is built for the string with the sentinel appended, so it holds
+/// `n + 1` suffixes, where `n` is the length of the string, with the sentinel suffix first.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::SuffixArray;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let sa = SuffixArray::new(x);
+///
+/// let mut matches: Vec<usize> = sa.search("ssi").collect();
+/// matches.sort();
+/// assert_eq!(matches, vec![2, 5]);
+/// ```
+pub struct SuffixArray<Char: CharacterTrait> {
+    /// The string the suffix array is built over
+    x: Str<Char>,
+    /// The suffix array
+    sa: Vec<usize>,
+}
+
+impl<Char: CharacterTrait> SuffixArray<Char> {
+    /// Builds the suffix array for the string `x` using SA-IS.
+    ///
+    /// The construction runs in O(n) time, where `n` is the length of `x`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to build the suffix array over.
+    ///
+    /// # Returns
+    ///
+    /// A new `SuffixArray` instance.
+    pub fn new(x: Str<Char>) -> SuffixArray<Char> {
+        let sa = sais(&x);
+        SuffixArray { x, sa }
+    }
+
+    /// Returns the string the suffix array is built over.
+    pub fn text(&self) -> &Str<Char> {
+        &self.x
+    }
+
+    /// Returns the suffix array as a slice.
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Returns the number of suffixes in the suffix array, including the sentinel suffix.
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Always returns `false`, since the suffix array has at least the sentinel suffix.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Compares the suffix starting at `i` to the pattern `p`, looking only at
+    /// the first `p.len()` characters of the suffix.
+    fn compare_prefix(&self, i: usize, p: &[Char]) -> Ordering {
+        self.x[i..].iter().take(p.len()).cmp(p.iter())
+    }
+
+    /// Returns the interval in the suffix array of the suffixes that have `p` as a prefix.
+    ///
+    /// The search is a binary search that runs in O(m log n) time, where `m` is the
+    /// length of the pattern and `n` is the length of the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for. It must use the same alphabet as the string.
+    ///
+    /// # Returns
+    ///
+    /// The range of indices into the suffix array whose suffixes start with `p`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use stralg::suffix_array::SuffixArray;
+    /// use stralg::utils::{Alphabet, Str};
+    ///
+    /// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+    /// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+    /// let p = x.translate_to_this_alphabet("ssi").unwrap();
+    /// let sa = SuffixArray::new(x);
+    /// assert_eq!(sa.interval(&p), 10..12);
+    /// ```
+    pub fn interval<S: AsStrSlice<Char>>(&self, p: &S) -> std::ops::Range<usize> {
+        let p = p.as_str_slice();
+        let lower = self
+            .sa
+            .partition_point(|&i| self.compare_prefix(i, p.as_slice()) == Ordering::Less);
+        let upper = lower
+            + self.sa[lower..]
+                .partition_point(|&i| self.compare_prefix(i, p.as_slice()) == Ordering::Equal);
+        lower..upper
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// `p` in the string.
+    ///
+    /// The occurrences are reported in the order their suffixes have in the suffix
+    /// array, not in the order they have in the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern `p`.
+    pub fn search(&self, p: &str) -> Box<dyn Iterator<Item = usize> + Send + '_> {
+        if p.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        let p = match self.x.translate_to_this_alphabet(p) {
+            Ok(p) => p,
+            Err(_) => return Box::new(std::iter::empty()),
+        };
+        Box::new(self.sa[self.interval(&p)].iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{naive, Alphabet};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_suffix_array_search() {
+        let x = "abracadabra";
+        let alphabet = Arc::new(Alphabet::from_str(x));
+        let sa = SuffixArray::new(Str::<u8>::from_str(x, &alphabet).unwrap());
+        for p in [
+            "a",
+            "ab",
+            "abra",
+            "bra",
+            "cad",
+            "abracadabra",
+            "d",
+            "dabrax",
+        ] {
+            let mut result: Vec<usize> = sa.search(p).collect();
+            result.sort();
+            let expected: Vec<usize> = naive(x, p).collect();
+            assert_eq!(result, expected, "p = {p}");
+        }
+    }
+
+    #[test]
+    fn test_suffix_array_search_not_in_alphabet() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let sa = SuffixArray::new(Str::<u8>::from_str("abcabc", &alphabet).unwrap());
+        assert_eq!(sa.search("abx").count(), 0);
+        assert_eq!(sa.search("").count(), 0);
+    }
+
+    #[test]
+    fn test_empty_text_has_sentinel_suffix() {
+        let alphabet = Arc::new(Alphabet::from_str(""));
+        let sa = SuffixArray::new(Str::<u8>::from_str("", &alphabet).unwrap());
+        assert_eq!(sa.sa(), &[0]);
+        assert_eq!(sa.len(), 1);
+        assert!(!sa.is_empty());
+    }
+}
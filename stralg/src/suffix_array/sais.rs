@@ -0,0 +1,289 @@
+use crate::utils::{AsStrSlice, CharacterTrait};
+
+/// Marks an empty slot in the suffix array while we build it.
+const EMPTY: usize = usize::MAX;
+
+/// The symbols of the strings we sort suffixes of. These are the characters of the
+/// input string at the top level and the names of LMS substrings in the recursion.
+trait Symbol: Copy {
+    /// The symbol's rank, which we use as its bucket index
+    fn rank(self) -> usize;
+}
+
+impl<Char: CharacterTrait> Symbol for Char {
+    fn rank(self) -> usize {
+        self.to_usize()
+    }
+}
+
+impl Symbol for usize {
+    fn rank(self) -> usize {
+        self
+    }
+}
+
+/// Computes the start index of each bucket, i.e., the index in the suffix array
+/// where the first suffix starting with each character goes.
+fn bucket_starts(counts: &[usize]) -> Vec<usize> {
+    let mut starts = Vec::with_capacity(counts.len());
+    let mut sum = 0;
+    for &count in counts {
+        starts.push(sum);
+        sum += count;
+    }
+    starts
+}
+
+/// Computes the end index (one past the last index) of each bucket.
+fn bucket_ends(counts: &[usize]) -> Vec<usize> {
+    let mut ends = Vec::with_capacity(counts.len());
+    let mut sum = 0;
+    for &count in counts {
+        sum += count;
+        ends.push(sum);
+    }
+    ends
+}
+
+/// Classifies each suffix as an S-suffix (`true`) or an L-suffix (`false`).
+///
+/// A suffix is an S-suffix if it is smaller than the suffix that follows it,
+/// and an L-suffix if it is larger. The last suffix, the sentinel, is an S-suffix.
+fn classify<T: Symbol>(s: &[T]) -> Vec<bool> {
+    let n = s.len();
+    let mut is_s = vec![true; n];
+    for i in (0..(n - 1)).rev() {
+        let (a, b) = (s[i].rank(), s[i + 1].rank());
+        is_s[i] = a < b || (a == b && is_s[i + 1]);
+    }
+    is_s
+}
+
+/// Left-most S-suffixes, i.e., S-suffixes that follow an L-suffix.
+fn is_lms(is_s: &[bool], i: usize) -> bool {
+    i > 0 && is_s[i] && !is_s[i - 1]
+}
+
+/// Induces the order of the L-suffixes from the suffixes already in `sa`,
+/// and then the order of the S-suffixes from the L-suffixes.
+fn induce<T: Symbol>(s: &[T], is_s: &[bool], counts: &[usize], sa: &mut [usize]) {
+    let mut heads = bucket_starts(counts);
+    for i in 0..sa.len() {
+        let j = sa[i];
+        if j != EMPTY && j > 0 && !is_s[j - 1] {
+            let a = s[j - 1].rank();
+            sa[heads[a]] = j - 1;
+            heads[a] += 1;
+        }
+    }
+    let mut tails = bucket_ends(counts);
+    for i in (0..sa.len()).rev() {
+        let j = sa[i];
+        if j != EMPTY && j > 0 && is_s[j - 1] {
+            let a = s[j - 1].rank();
+            tails[a] -= 1;
+            sa[tails[a]] = j - 1;
+        }
+    }
+}
+
+/// Checks if the LMS substrings starting at `a` and `b` are equal, i.e., if they have
+/// the same characters and types up to and including the next LMS position.
+fn equal_lms_substrings<T: Symbol>(s: &[T], is_s: &[bool], a: usize, b: usize) -> bool {
+    let n = s.len();
+    if a == n - 1 || b == n - 1 {
+        return a == b; // The sentinel is unique
+    }
+    let mut i = 0;
+    loop {
+        if s[a + i].rank() != s[b + i].rank() || is_s[a + i] != is_s[b + i] {
+            return false;
+        }
+        if i > 0 {
+            let (a_end, b_end) = (is_lms(is_s, a + i), is_lms(is_s, b + i));
+            if a_end || b_end {
+                return a_end && b_end;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Builds the suffix array of `s` with the SA-IS algorithm.
+///
+/// The string must end with a unique sentinel that is smaller than all other
+/// characters, and all characters must be smaller than `sigma`.
+fn sais_impl<T: Symbol>(s: &[T], sigma: usize) -> Vec<usize> {
+    let n = s.len();
+    if n == 1 {
+        return vec![0];
+    }
+
+    let is_s = classify(s);
+    let mut counts = vec![0; sigma];
+    for &a in s {
+        counts[a.rank()] += 1;
+    }
+
+    // Sort the LMS substrings by placing the LMS suffixes at the ends of their
+    // buckets, in any order, and inducing the rest.
+    let mut sa = vec![EMPTY; n];
+    let mut tails = bucket_ends(&counts);
+    for i in (1..n).filter(|&i| is_lms(&is_s, i)) {
+        let a = s[i].rank();
+        tails[a] -= 1;
+        sa[tails[a]] = i;
+    }
+    induce(s, &is_s, &counts, &mut sa);
+
+    // Name the LMS substrings by their rank, so equal substrings get the same name.
+    // We store the names at index i/2 of the names vector, since LMS positions
+    // are at least two apart.
+    let mut names = vec![EMPTY; n / 2 + 1];
+    let mut name = 0;
+    let mut prev: Option<usize> = None;
+    for &j in sa.iter().filter(|&&j| is_lms(&is_s, j)) {
+        if let Some(prev) = prev {
+            if !equal_lms_substrings(s, &is_s, prev, j) {
+                name += 1;
+            }
+        }
+        names[j / 2] = name;
+        prev = Some(j);
+    }
+    let lms_positions: Vec<usize> = (1..n).filter(|&i| is_lms(&is_s, i)).collect();
+    let reduced: Vec<usize> = lms_positions.iter().map(|&i| names[i / 2]).collect();
+
+    // Sort the LMS suffixes, recursively if the names are not unique.
+    let reduced_sa = if name + 1 < reduced.len() {
+        sais_impl(&reduced, name + 1)
+    } else {
+        let mut reduced_sa = vec![0; reduced.len()];
+        for (i, &name) in reduced.iter().enumerate() {
+            reduced_sa[name] = i;
+        }
+        reduced_sa
+    };
+
+    // Place the sorted LMS suffixes at the ends of their buckets, and induce the rest.
+    sa.fill(EMPTY);
+    let mut tails = bucket_ends(&counts);
+    for &r in reduced_sa.iter().rev() {
+        let i = lms_positions[r];
+        let a = s[i].rank();
+        tails[a] -= 1;
+        sa[tails[a]] = i;
+    }
+    induce(s, &is_s, &counts, &mut sa);
+
+    sa
+}
+
+/// Computes the suffix array of the string `x` using the SA-IS algorithm.
+///
+/// The suffix array is computed for `x` with the sentinel (zero) appended, so it
+/// has length `n + 1`, where `n` is the length of `x`, and the first entry is always
+/// `n`, the position of the sentinel suffix. Since the alphabet maps characters to the
+/// dense range `1..=σ`, the characters can be used directly as bucket indices.
+///
+/// The algorithm runs in O(n) time.
+///
+/// # Arguments
+///
+/// * `x` - The string for which to compute the suffix array.
+///
+/// # Returns
+///
+/// A vector containing the suffix array of `x` followed by the sentinel.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_array::sais;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let sa = sais(&x);
+/// assert_eq!(sa, vec![11, 10, 7, 4, 1, 0, 9, 8, 6, 3, 5, 2]);
+/// ```
+pub fn sais<Char, S>(x: &S) -> Vec<usize>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+{
+    let x = x.as_str_slice();
+    let sentinel = Char::try_from(0).unwrap();
+    let mut s: Vec<Char> = Vec::with_capacity(x.len() + 1);
+    s.extend(x.iter());
+    s.push(sentinel);
+    sais_impl(&s, x.alphabet.len() + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::Str;
+    use crate::Alphabet;
+    use std::sync::Arc;
+
+    /// Sorts the suffixes directly, for comparison.
+    fn naive_suffix_array(x: &Str<u8>) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..=x.len()).collect();
+        sa.sort_by(|&i, &j| x[i..].cmp(&x[j..]));
+        sa
+    }
+
+    #[test]
+    fn test_sais_mississippi() {
+        let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+        let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+        assert_eq!(sais(&x), vec![11, 10, 7, 4, 1, 0, 9, 8, 6, 3, 5, 2]);
+    }
+
+    #[test]
+    fn test_sais_empty() {
+        let alphabet = Arc::new(Alphabet::from_str(""));
+        let x: Str<u8> = Str::from_str("", &alphabet).unwrap();
+        assert_eq!(sais(&x), vec![0]);
+    }
+
+    #[test]
+    fn test_sais_matches_naive() {
+        let strings = [
+            "a",
+            "aaaaaaaa",
+            "abababab",
+            "abracadabra",
+            "aabaabaaabaabaaab",
+            "cabbagecabbagebag",
+            "gtccgatgtcgcaagtcc",
+            "babbababbabbababbababbabbababbab",
+        ];
+        for x in strings {
+            let alphabet = Arc::new(Alphabet::from_str(x));
+            let x: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+            assert_eq!(sais(&x), naive_suffix_array(&x));
+        }
+    }
+
+    #[test]
+    fn test_sais_all_binary_strings() {
+        let alphabet = Arc::new(Alphabet::from_str("ab"));
+        for n in 1..=10 {
+            for bits in 0..(1u32 << n) {
+                let chars = (0..n).map(|k| 1 + ((bits >> k) & 1) as u8).collect();
+                let x = Str::new(chars, &alphabet);
+                assert_eq!(sais(&x), naive_suffix_array(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sais_u16() {
+        let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+        let x: Str<u16> = Str::from_str("mississippi", &alphabet).unwrap();
+        assert_eq!(sais(&x), vec![11, 10, 7, 4, 1, 0, 9, 8, 6, 3, 5, 2]);
+    }
+}
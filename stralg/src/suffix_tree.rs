@@ -0,0 +1,658 @@
+use crate::utils::{AsStrSlice, CharTable, CharacterTrait, Str};
+
+/// Marks a missing child in the child table.
+const NO_NODE: usize = usize::MAX;
+
+/// A node in the suffix tree.
+struct Node {
+    /// The start of the label on the edge into the node
+    start: usize,
+    /// The end of the label on the edge into the node
+    end: usize,
+    /// The length of the string spelled out by the path from the root to the node
+    depth: usize,
+    /// The parent of the node (the root is its own parent)
+    parent: usize,
+    /// The suffix link of the node, if we have computed it
+    suffix_link: usize,
+    /// The row of an inner node's children in the child table
+    children: Option<usize>,
+    /// The index of the suffix a leaf represents
+    leaf: Option<usize>,
+}
+
+/// A suffix tree over a string, built with McCreight's algorithm.
+///
+/// The tree is built over the string followed by the sentinel (zero), so every
+/// suffix ends in a leaf. Nodes are identified by their index, the root is node
+/// [`SuffixTree::root`], and edge labels are ranges into [`SuffixTree::text`]. The
+/// children of each node are stored in a table indexed by the alphabet rank of the
+/// first character on their edge, so a depth first traversal visits the leaves in
+/// suffix array order. For small alphabets child lookup takes constant time, and for
+/// large alphabets the table only stores the children each node has.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_tree::SuffixTree;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_str("mississippi"));
+/// let x: Str<u8> = Str::from_str("mississippi", &alphabet).unwrap();
+/// let tree = SuffixTree::new(&x);
+///
+/// let mut matches: Vec<usize> = tree.search("ssi").collect();
+/// matches.sort();
+/// assert_eq!(matches, vec![2, 5]);
+/// assert_eq!(tree.longest_repeat(), Some(1..5)); // issi
+/// ```
+pub struct SuffixTree<Char: CharacterTrait> {
+    /// The string followed by the sentinel
+    s: Str<Char>,
+    /// The nodes, with the root at index zero
+    nodes: Vec<Node>,
+    /// The children of the inner nodes, with a row per inner node
+    children: CharTable<usize>,
+}
+
+/// A visitor for depth first traversals of a [`SuffixTree`].
+///
+/// Both methods have empty default implementations, so a visitor only needs to
+/// implement the ones it uses.
+pub trait SuffixTreeVisitor<Char: CharacterTrait> {
+    /// Called when the traversal reaches node `v`, before its children.
+    fn pre_order(&mut self, _tree: &SuffixTree<Char>, _v: usize) {}
+
+    /// Called when the traversal leaves node `v`, after its children.
+    fn post_order(&mut self, _tree: &SuffixTree<Char>, _v: usize) {}
+}
+
+impl<Char: CharacterTrait> SuffixTree<Char> {
+    /// Builds the suffix tree for the string `x` using McCreight's algorithm.
+    ///
+    /// The construction runs in O(nσ) time, where `n` is the length of `x` and σ is
+    /// the size of its alphabet, where the σ factor comes from the child tables. For
+    /// large alphabets the child tables only store the children each node has, so
+    /// the tree takes O(n) space.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The string to build the suffix tree over.
+    ///
+    /// # Returns
+    ///
+    /// A new `SuffixTree` instance.
+    pub fn new<S: AsStrSlice<Char>>(x: &S) -> SuffixTree<Char> {
+        let x = x.as_str_slice();
+        let sentinel = Char::try_from(0).unwrap();
+        let mut s = Vec::with_capacity(x.len() + 1);
+        s.extend(x.iter());
+        s.push(sentinel);
+        Self::from_terminated(Str::new(s, x.alphabet), x.alphabet.len() + 1)
+    }
+
+    /// Builds the suffix tree for `s`, which must end with a unique sentinel and only
+    /// contain characters smaller than `sigma`.
+    fn from_terminated(s: Str<Char>, sigma: usize) -> SuffixTree<Char> {
+        let mut children = CharTable::new(sigma);
+        let root = Node {
+            start: 0,
+            end: 0,
+            depth: 0,
+            parent: 0,
+            suffix_link: 0,
+            children: Some(children.add_row(NO_NODE)),
+            leaf: None,
+        };
+        let mut tree = SuffixTree {
+            s,
+            nodes: vec![root],
+            children,
+        };
+        tree.mccreight();
+        tree
+    }
+
+    fn child(&self, v: usize, a: Char) -> Option<usize> {
+        match self.children.get(self.nodes[v].children?, a.to_usize()) {
+            NO_NODE => None,
+            w => Some(w),
+        }
+    }
+
+    /// Makes `w` the child of `v` for the character `a`.
+    fn set_child(&mut self, v: usize, a: Char, w: usize) {
+        let row = self.nodes[v].children.unwrap(); // Only inner nodes get children
+        self.children.set(row, a.to_usize(), w);
+    }
+
+    fn edge_len(&self, v: usize) -> usize {
+        self.nodes[v].end - self.nodes[v].start
+    }
+
+    /// Splits the edge into `v` after `k` characters, and returns the new node.
+    fn split(&mut self, v: usize, k: usize) -> usize {
+        let w = self.nodes.len();
+        let Node { start, parent, .. } = self.nodes[v];
+        let depth = self.nodes[parent].depth + k;
+        let children = self.children.add_row(NO_NODE);
+        self.nodes.push(Node {
+            start,
+            end: start + k,
+            depth,
+            parent,
+            suffix_link: NO_NODE,
+            children: Some(children),
+            leaf: None,
+        });
+        self.set_child(w, self.s[start + k], v);
+        self.set_child(parent, self.s[start], w);
+        self.nodes[v].start += k;
+        self.nodes[v].parent = w;
+        w
+    }
+
+    /// Adds a leaf for suffix `i` below `v`, with the label starting at `from`.
+    fn add_leaf(&mut self, v: usize, from: usize, i: usize) {
+        let w = self.nodes.len();
+        let n = self.s.len();
+        self.nodes.push(Node {
+            start: from,
+            end: n,
+            depth: n - i,
+            parent: v,
+            suffix_link: NO_NODE,
+            children: None,
+            leaf: Some(i),
+        });
+        self.set_child(v, self.s[from], w);
+    }
+
+    /// Moves down from `v` along the `len` characters starting at `from`, which we
+    /// know are in the tree, so we only compare the first character on each edge.
+    /// Returns the node at the end of the path, creating it if it is on an edge.
+    fn fast_scan(&mut self, mut v: usize, mut from: usize, mut len: usize) -> usize {
+        while len > 0 {
+            let w = self.child(v, self.s[from]).unwrap();
+            let edge_len = self.edge_len(w);
+            if edge_len > len {
+                return self.split(w, len);
+            }
+            v = w;
+            from += edge_len;
+            len -= edge_len;
+        }
+        v
+    }
+
+    /// Moves down from `v` along the string starting at `from` for as long as it
+    /// matches the tree. Returns the node where the match ends, creating it if it is
+    /// on an edge, and the position in the string where the match failed.
+    fn slow_scan(&mut self, mut v: usize, mut from: usize) -> (usize, usize) {
+        loop {
+            let w = match self.child(v, self.s[from]) {
+                Some(w) => w,
+                None => return (v, from),
+            };
+            let start = self.nodes[w].start;
+            let edge_len = self.edge_len(w);
+            let mut k = 1; // We already know the first character matches
+            while k < edge_len && self.s[start + k] == self.s[from + k] {
+                k += 1;
+            }
+            if k < edge_len {
+                return (self.split(w, k), from + k);
+            }
+            v = w;
+            from += k;
+        }
+    }
+
+    /// Inserts the suffixes from the longest to the shortest with McCreight's algorithm.
+    ///
+    /// When we insert suffix `i`, the head of the previous suffix, where its leaf
+    /// hangs, is `a·β` for some character `a`, so `β` must also be in the tree, and
+    /// we can jump to it with the suffix link of head's parent and a fast scan. From
+    /// there we slow scan to find the head of suffix `i`.
+    fn mccreight(&mut self) {
+        const ROOT: usize = 0;
+        let (mut head, from) = self.slow_scan(ROOT, 0);
+        self.add_leaf(head, from, 0);
+
+        for i in 1..self.s.len() {
+            let w = if head == ROOT {
+                ROOT
+            } else if self.nodes[head].suffix_link != NO_NODE {
+                self.nodes[head].suffix_link
+            } else {
+                let Node {
+                    start, end, parent, ..
+                } = self.nodes[head];
+                let w = if parent == ROOT {
+                    self.fast_scan(ROOT, start + 1, end - start - 1)
+                } else {
+                    let link = self.nodes[parent].suffix_link;
+                    self.fast_scan(link, start, end - start)
+                };
+                self.nodes[head].suffix_link = w;
+                w
+            };
+            let (h, from) = self.slow_scan(w, i + self.nodes[w].depth);
+            self.add_leaf(h, from, i);
+            head = h;
+        }
+    }
+
+    /// Returns the string the tree is built over, including the sentinel at the end.
+    pub fn text(&self) -> &Str<Char> {
+        &self.s
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    /// Returns the number of nodes in the tree.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the parent of node `v`. The root is its own parent.
+    pub fn parent(&self, v: usize) -> usize {
+        self.nodes[v].parent
+    }
+
+    /// Returns the range in [`SuffixTree::text`] of the label on the edge into node `v`.
+    pub fn edge(&self, v: usize) -> std::ops::Range<usize> {
+        self.nodes[v].start..self.nodes[v].end
+    }
+
+    /// Returns the label on the edge into node `v`.
+    pub fn edge_label(&self, v: usize) -> &[Char] {
+        &self.s[self.edge(v)]
+    }
+
+    /// Returns the length of the string spelled out by the path from the root to node `v`.
+    pub fn depth(&self, v: usize) -> usize {
+        self.nodes[v].depth
+    }
+
+    /// Returns the index of the suffix that leaf `v` represents, or `None` if `v` is
+    /// an inner node.
+    pub fn leaf_index(&self, v: usize) -> Option<usize> {
+        self.nodes[v].leaf
+    }
+
+    /// Returns `true` if `v` is a leaf.
+    pub fn is_leaf(&self, v: usize) -> bool {
+        self.nodes[v].leaf.is_some()
+    }
+
+    /// Returns an iterator over the children of node `v`, ordered by the first
+    /// character on their edges.
+    pub fn children(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes[v]
+            .children
+            .into_iter()
+            .flat_map(|row| self.children.entries(row, NO_NODE).map(|(_, w)| w))
+    }
+
+    /// Traverses the tree depth first from node `v`, calling the visitor's
+    /// `pre_order` and `post_order` methods on each node. Children are visited in
+    /// the order of the first character on their edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - The node to start the traversal from.
+    /// * `visitor` - The visitor to call on each node.
+    pub fn dfs_from<V>(&self, v: usize, visitor: &mut V)
+    where
+        V: SuffixTreeVisitor<Char> + ?Sized,
+    {
+        // We use an explicit stack, since the tree can be as deep as the string is long.
+        // The flag tells us if we are entering or leaving the node.
+        let mut stack = vec![(v, false)];
+        while let Some((v, leaving)) = stack.pop() {
+            if leaving {
+                visitor.post_order(self, v);
+                continue;
+            }
+            visitor.pre_order(self, v);
+            stack.push((v, true));
+            let children: Vec<usize> = self.children(v).collect();
+            stack.extend(children.into_iter().rev().map(|w| (w, false)));
+        }
+    }
+
+    /// Traverses the whole tree depth first, calling the visitor's `pre_order` and
+    /// `post_order` methods on each node.
+    ///
+    /// # Arguments
+    ///
+    /// * `visitor` - The visitor to call on each node.
+    pub fn dfs<V>(&self, visitor: &mut V)
+    where
+        V: SuffixTreeVisitor<Char> + ?Sized,
+    {
+        self.dfs_from(self.root(), visitor);
+    }
+
+    /// Returns an iterator over the suffix indices of the leaves below node `v`, in
+    /// lexicographical order of the suffixes.
+    pub fn leaves(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack = vec![v];
+        std::iter::from_fn(move || {
+            while let Some(v) = stack.pop() {
+                if let Some(i) = self.nodes[v].leaf {
+                    return Some(i);
+                }
+                let children: Vec<usize> = self.children(v).collect();
+                stack.extend(children.into_iter().rev());
+            }
+            None
+        })
+    }
+
+    /// Finds the node at or below the end of the path that spells out `p`, if
+    /// there is such a path.
+    fn locus(&self, p: &Str<Char>) -> Option<usize> {
+        let mut v = self.root();
+        let mut k = 0;
+        while k < p.len() {
+            v = self.child(v, p[k])?;
+            let label = self.edge_label(v);
+            for &a in label.iter().take(p.len() - k) {
+                if a != p[k] {
+                    return None;
+                }
+                k += 1;
+            }
+        }
+        Some(v)
+    }
+
+    /// Returns an iterator over the starting indices of occurrences of the pattern
+    /// `p` in the string.
+    ///
+    /// The search runs in O(m + z) time, where `m` is the length of the pattern and
+    /// `z` the number of occurrences. The occurrences are reported in the order their
+    /// suffixes have in the suffix array, not in the order they have in the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The pattern to search for.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the starting indices of occurrences of the pattern `p`.
+    pub fn search(&self, p: &str) -> Box<dyn Iterator<Item = usize> + Send + '_> {
+        if p.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+        let p = match self.s.translate_to_this_alphabet(p) {
+            Ok(p) => p,
+            Err(_) => return Box::new(std::iter::empty()),
+        };
+        match self.locus(&p) {
+            Some(v) => Box::new(self.leaves(v)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Returns the range in the string of the left-most occurrence of a longest
+    /// substring that occurs at least twice, or `None` if no character is repeated.
+    ///
+    /// The repeat is the path to the deepest inner node, so this runs in O(n) time.
+    pub fn longest_repeat(&self) -> Option<std::ops::Range<usize>> {
+        let v = (0..self.nodes.len())
+            .filter(|&v| !self.is_leaf(v))
+            .max_by_key(|&v| self.nodes[v].depth)?;
+        let depth = self.nodes[v].depth;
+        if depth == 0 {
+            return None;
+        }
+        let i = self.leaves(v).min().unwrap(); // Inner nodes have leaves below them
+        Some(i..(i + depth))
+    }
+}
+
+/// Collects, for each node, whether it has leaves from the first and the second string
+/// below it, and remembers the deepest node that has both.
+struct CommonSubstringVisitor {
+    /// The position of the separator between the two strings
+    separator: usize,
+    /// Bit 1 is set if a node has a leaf from the first string, bit 2 from the second
+    below: Vec<u8>,
+    /// The deepest node with leaves from both strings, and its depth
+    best: Option<(usize, usize)>,
+}
+
+impl<Char: CharacterTrait> SuffixTreeVisitor<Char> for CommonSubstringVisitor {
+    fn post_order(&mut self, tree: &SuffixTree<Char>, v: usize) {
+        self.below[v] = match tree.leaf_index(v) {
+            Some(i) if i < self.separator => 1,
+            Some(i) if i > self.separator => 2,
+            Some(_) => 0, // The suffix that starts with the separator is in neither
+            None => tree.children(v).fold(0, |acc, w| acc | self.below[w]),
+        };
+        // The root has depth zero, so it is never the best node, even if both strings are below it
+        if self.below[v] == 3 && tree.depth(v) > self.best.map_or(0, |(_, d)| d) {
+            self.best = Some((v, tree.depth(v)));
+        }
+    }
+}
+
+/// Finds a longest common substring of `x` and `y` using a generalised suffix tree.
+///
+/// The tree is built over `x#y$`, where `#` is a separator that is not in the alphabet
+/// and `$` is the sentinel. A common substring is the label of a path to a node with
+/// leaves from both strings below it, so we find the deepest such node.
+///
+/// The algorithm runs in O((n + m)σ) time, where `n` and `m` are the lengths of the
+/// strings and σ is the size of their alphabet.
+///
+/// # Arguments
+///
+/// * `x` - The first string.
+/// * `y` - The second string. It must use the same alphabet as `x`.
+///
+/// # Returns
+///
+/// The ranges in `x` and in `y` of a longest common substring, or `None` if the
+/// strings have no characters in common.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use stralg::suffix_tree::longest_common_substring;
+/// use stralg::utils::{Alphabet, Str};
+///
+/// let alphabet = Arc::new(Alphabet::from_strs(&["abracadabra", "cadabrac"]));
+/// let x: Str<u8> = Str::from_str("abracadabra", &alphabet).unwrap();
+/// let y: Str<u8> = Str::from_str("cadabrac", &alphabet).unwrap();
+/// assert_eq!(longest_common_substring(&x, &y), Some((4..11, 0..7))); // cadabra
+/// ```
+pub fn longest_common_substring<Char, S, T>(
+    x: &S,
+    y: &T,
+) -> Option<(std::ops::Range<usize>, std::ops::Range<usize>)>
+where
+    Char: CharacterTrait,
+    S: AsStrSlice<Char>,
+    T: AsStrSlice<Char>,
+{
+    let x = x.as_str_slice();
+    let y = y.as_str_slice();
+    // The alphabet leaves room for one character more than its size, which we
+    // use for the separator.
+    let sigma = x.alphabet.len() + 1;
+    let separator = Char::try_from(sigma).unwrap();
+    let sentinel = Char::try_from(0).unwrap();
+    let mut s = Vec::with_capacity(x.len() + y.len() + 2);
+    s.extend(x.iter());
+    s.push(separator);
+    s.extend(y.iter());
+    s.push(sentinel);
+    let tree = SuffixTree::from_terminated(Str::new(s, x.alphabet), sigma + 1);
+
+    let mut visitor = CommonSubstringVisitor {
+        separator: x.len(),
+        below: vec![0; tree.node_count()],
+        best: None,
+    };
+    tree.dfs(&mut visitor);
+    let (v, depth) = visitor.best?;
+    // Report the left-most occurrences. The node has leaves from both strings below it.
+    let (xs, ys): (Vec<usize>, Vec<usize>) = tree.leaves(v).partition(|&i| i < x.len());
+    let i = xs.into_iter().min().unwrap();
+    let j = ys.into_iter().min().unwrap() - x.len() - 1;
+    Some((i..(i + depth), j..(j + depth)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::suffix_array::sais;
+    use crate::{naive, Alphabet};
+    use std::sync::Arc;
+
+    fn build(x: &str) -> SuffixTree<u8> {
+        let alphabet = Arc::new(Alphabet::from_str(x));
+        SuffixTree::new(&Str::from_str(x, &alphabet).unwrap())
+    }
+
+    #[test]
+    fn test_leaves_in_suffix_array_order() {
+        for x in [
+            "",
+            "a",
+            "aaaa",
+            "abracadabra",
+            "mississippi",
+            "aabaabaaabaab",
+        ] {
+            let alphabet = Arc::new(Alphabet::from_str(x));
+            let s: Str<u8> = Str::from_str(x, &alphabet).unwrap();
+            let tree = SuffixTree::new(&s);
+            let leaves: Vec<usize> = tree.leaves(tree.root()).collect();
+            assert_eq!(leaves, sais(&s), "x = {x}");
+        }
+    }
+
+    #[test]
+    fn test_all_binary_strings() {
+        let alphabet = Arc::new(Alphabet::from_str("ab"));
+        for n in 1..=9 {
+            for bits in 0..(1u32 << n) {
+                let chars = (0..n).map(|k| 1 + ((bits >> k) & 1) as u8).collect();
+                let s = Str::new(chars, &alphabet);
+                let tree = SuffixTree::new(&s);
+                let leaves: Vec<usize> = tree.leaves(tree.root()).collect();
+                assert_eq!(leaves, sais(&s));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_alphabet() {
+        // Too many characters for dense child tables
+        let x: String = (0..2000)
+            .map(|i| std::char::from_u32(0x4e00 + (i * i) % 500).unwrap())
+            .collect();
+        let alphabet = Arc::new(Alphabet::from_str(&x));
+        let s: Str<u16> = Str::from_str(&x, &alphabet).unwrap();
+        let tree = SuffixTree::new(&s);
+        let leaves: Vec<usize> = tree.leaves(tree.root()).collect();
+        assert_eq!(leaves, sais(&s));
+        let p: String = x.chars().skip(700).take(5).collect();
+        let mut result: Vec<usize> = tree.search(&p).collect();
+        result.sort();
+        let expected: Vec<usize> = naive(&x, &p).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_tree_structure() {
+        let tree = build("mississippi");
+        for v in 0..tree.node_count() {
+            if v == tree.root() {
+                continue;
+            }
+            let parent = tree.parent(v);
+            assert_eq!(tree.depth(v), tree.depth(parent) + tree.edge_label(v).len());
+            if !tree.is_leaf(v) {
+                // Inner nodes branch
+                assert!(tree.children(v).count() >= 2);
+            }
+        }
+        // n + 1 leaves, and at most n inner nodes
+        let leaves = (0..tree.node_count()).filter(|&v| tree.is_leaf(v)).count();
+        assert_eq!(leaves, 12);
+    }
+
+    #[test]
+    fn test_search_matches_naive() {
+        let x = "aabaabaaabaabbaabaaab";
+        let tree = build(x);
+        for p in [
+            "a",
+            "b",
+            "aab",
+            "baa",
+            "abba",
+            "bbb",
+            "aabaabaaabaabbaabaaab",
+            "c",
+        ] {
+            let mut result: Vec<usize> = tree.search(p).collect();
+            result.sort();
+            let expected: Vec<usize> = naive(x, p).collect();
+            assert_eq!(result, expected, "p = {p}");
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        struct LeafCounter {
+            count: usize,
+            depth_sum: usize,
+        }
+        impl SuffixTreeVisitor<u8> for LeafCounter {
+            fn pre_order(&mut self, tree: &SuffixTree<u8>, v: usize) {
+                if tree.is_leaf(v) {
+                    self.count += 1;
+                    self.depth_sum += tree.depth(v);
+                }
+            }
+        }
+        let tree = build("abracadabra");
+        let mut counter = LeafCounter {
+            count: 0,
+            depth_sum: 0,
+        };
+        tree.dfs(&mut counter);
+        assert_eq!(counter.count, 12);
+        assert_eq!(counter.depth_sum, (1..=12).sum());
+    }
+
+    #[test]
+    fn test_longest_repeat() {
+        assert_eq!(build("abracadabra").longest_repeat(), Some(0..4));
+        assert_eq!(build("aaaa").longest_repeat(), Some(0..3));
+        assert_eq!(build("abcd").longest_repeat(), None);
+        assert_eq!(build("").longest_repeat(), None);
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let alphabet = Arc::new(Alphabet::from_strs(&["xabcy", "zzabcz", "q"]));
+        let x: Str<u8> = Str::from_str("xabcy", &alphabet).unwrap();
+        let y: Str<u8> = Str::from_str("zzabcz", &alphabet).unwrap();
+        let z: Str<u8> = Str::from_str("q", &alphabet).unwrap();
+        assert_eq!(longest_common_substring(&x, &y), Some((1..4, 2..5)));
+        assert_eq!(longest_common_substring(&x, &z), None);
+    }
+}
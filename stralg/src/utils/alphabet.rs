@@ -1,19 +1,25 @@
 use std::collections::HashMap;
 
 use super::char::{CharSize, CharacterTrait};
+use crate::Error;
 use std::collections::HashSet;
+use std::sync::OnceLock;
 
 /// An alphabet we can have strings over.
 ///
 /// This is predominantly used for mapping UTF-8 str strings to vectors where we have
 /// constant time access to the characters, without relying on a Vec<char> which would take
 /// up four bytes per character.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Alphabet {
     /// A vector of characters storing the alphabet in a specific order.
     chars: Vec<char>,
     /// A hash map mapping each character to its index in the `chars` vector.
     indices: HashMap<char, usize>,
+    /// The index of each byte, read as the character with the same code point, so
+    /// bytes can be mapped without hashing. Bytes not in the alphabet map to zero.
+    /// The table is built the first time we map bytes.
+    byte_indices: OnceLock<Vec<usize>>,
 }
 
 impl Alphabet {
@@ -49,7 +55,11 @@ impl Alphabet {
             indices.insert(c, i + 1); // The +1 is to leave room for the sentinel at zero
         }
 
-        Alphabet { chars, indices }
+        Alphabet {
+            chars,
+            indices,
+            byte_indices: OnceLock::new(),
+        }
     }
 
     /// Creates a new `Alphabet` from a string.
@@ -75,6 +85,7 @@ impl Alphabet {
     /// assert_eq!(alphabet.index('b'), Some(2));
     /// assert_eq!(alphabet.len(), 3);
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Alphabet {
         let chars: Vec<char> = s.chars().collect();
         Alphabet::new(&chars)
@@ -133,6 +144,207 @@ impl Alphabet {
         Alphabet::new(&chars)
     }
 
+    /// Creates a new `Alphabet` from a slice of bytes.
+    ///
+    /// Each byte is read as the character with the same code point (Latin-1), so
+    /// the alphabet can hold any byte and the bytes are ranked in their natural
+    /// order. Strings over the alphabet are created from bytes with
+    /// [`Alphabet::map_bytes`], and no UTF-8 decoding is involved.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - A slice of bytes to include in the alphabet.
+    ///
+    /// # Returns
+    ///
+    /// A `Alphabet` containing the bytes and their corresponding indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::from_bytes(&[0xff, 0x00, 0x41]);
+    /// assert_eq!(alphabet.len(), 3);
+    /// assert_eq!(alphabet.index('\0'), Some(1));
+    /// assert_eq!(alphabet.index('A'), Some(2));
+    /// assert_eq!(alphabet.index('\u{ff}'), Some(3));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Alphabet {
+        let mut seen = [false; 256];
+        let chars: Vec<char> = bytes
+            .iter()
+            .filter(|&&b| !std::mem::replace(&mut seen[b as usize], true))
+            .map(|&b| char::from(b))
+            .collect();
+        Alphabet::new(&chars)
+    }
+
+    /// Creates the alphabet of all 256 bytes, where byte `b` has rank `b + 1`.
+    ///
+    /// Texts over this alphabet can contain any byte, but with the sentinel the
+    /// alphabet needs 257 characters, so strings over it need `u16` characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::{Alphabet, CharSize};
+    ///
+    /// let alphabet = Alphabet::bytes();
+    /// assert_eq!(alphabet.len(), 256);
+    /// assert_eq!(alphabet.char_size().unwrap(), CharSize::U16);
+    /// let x: Vec<u16> = alphabet.map_bytes(b"\x00AB\xff").unwrap();
+    /// assert_eq!(x, vec![1, 66, 67, 256]);
+    /// ```
+    pub fn bytes() -> Alphabet {
+        let chars: Vec<char> = (0..=u8::MAX).map(char::from).collect();
+        Alphabet::new(&chars)
+    }
+
+    /// Creates the DNA alphabet, `ACGT`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::dna();
+    /// assert_eq!(alphabet.len(), 4);
+    /// assert_eq!(alphabet.index('T'), Some(4));
+    /// ```
+    pub fn dna() -> Alphabet {
+        Alphabet::from_str("ACGT")
+    }
+
+    /// Creates the IUPAC nucleotide alphabet: `ACGT`, `U` for RNA, the ambiguity
+    /// codes `RYSWKMBDHVN`, and `-` for gaps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::iupac();
+    /// assert_eq!(alphabet.len(), 17);
+    /// assert!(alphabet.contains('N'));
+    /// assert!(alphabet.contains('-'));
+    /// ```
+    pub fn iupac() -> Alphabet {
+        Alphabet::from_str("ACGTURYSWKMBDHVN-")
+    }
+
+    /// Creates the alphabet of the 20 standard amino acids.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::protein();
+    /// assert_eq!(alphabet.len(), 20);
+    /// assert!(!alphabet.contains('X'));
+    /// ```
+    pub fn protein() -> Alphabet {
+        Alphabet::from_str("ACDEFGHIKLMNPQRSTVWY")
+    }
+
+    /// Creates the 25 letter protein alphabet: the 20 standard amino acids,
+    /// selenocysteine `U` and pyrrolysine `O`, and the ambiguity codes `B`, `Z`
+    /// and `X`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::protein_extended();
+    /// assert_eq!(alphabet.len(), 25);
+    /// assert!(alphabet.contains('X'));
+    /// ```
+    pub fn protein_extended() -> Alphabet {
+        Alphabet::from_str("ACDEFGHIKLMNPQRSTVWYUOBZX")
+    }
+
+    /// Creates the alphabet of all 128 ASCII characters, including the control
+    /// characters.
+    ///
+    /// Note that `'\0'` is a character in this alphabet, with rank 1, so it is
+    /// not confused with the sentinel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::{Alphabet, CharSize};
+    ///
+    /// let alphabet = Alphabet::ascii();
+    /// assert_eq!(alphabet.len(), 128);
+    /// assert_eq!(alphabet.index('A'), Some(66));
+    /// assert_eq!(alphabet.char_size().unwrap(), CharSize::U8);
+    /// ```
+    pub fn ascii() -> Alphabet {
+        let chars: Vec<char> = (0..=0x7f).map(char::from).collect();
+        Alphabet::new(&chars)
+    }
+
+    /// Creates the alphabet of the 95 printable ASCII characters, from space to `~`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::printable();
+    /// assert_eq!(alphabet.len(), 95);
+    /// assert_eq!(alphabet.index(' '), Some(1));
+    /// assert!(!alphabet.contains('\n'));
+    /// ```
+    pub fn printable() -> Alphabet {
+        let chars: Vec<char> = (0x20..=0x7e).map(char::from).collect();
+        Alphabet::new(&chars)
+    }
+
+    /// Makes the alphabet case-insensitive, so the other case of each letter maps
+    /// to the same rank as the letter itself.
+    ///
+    /// The size of the alphabet and the ranks of its characters do not change, so
+    /// strings mapped with and without case-insensitivity can be compared. Letters
+    /// whose other case is already in the alphabet keep their own rank, and letters
+    /// whose other case is more than one character are left alone. Decoding a
+    /// string gives back the characters of the original alphabet.
+    ///
+    /// # Returns
+    ///
+    /// The case-insensitive alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::dna().ignore_case();
+    /// assert_eq!(alphabet.len(), 4);
+    /// assert_eq!(alphabet.index('g'), alphabet.index('G'));
+    /// let x: Vec<u8> = alphabet.map_str("gAtc").unwrap();
+    /// assert_eq!(x, vec![3, 1, 4, 2]);
+    /// assert_eq!(alphabet.char_at(3), Some('G'));
+    /// ```
+    pub fn ignore_case(mut self) -> Alphabet {
+        for (i, &c) in self.chars.iter().enumerate() {
+            for other in [single_char(c.to_lowercase()), single_char(c.to_uppercase())]
+                .into_iter()
+                .flatten()
+            {
+                if self.indices.contains_key(&other) {
+                    continue;
+                }
+                self.indices.insert(other, i + 1);
+            }
+        }
+        // The byte table must be rebuilt with the new indices
+        self.byte_indices.take();
+        self
+    }
+
     /// Checks if the alphabet contains the given character.
     ///
     /// # Arguments
@@ -203,6 +415,46 @@ impl Alphabet {
         self.chars.len()
     }
 
+    /// Returns `true` if the alphabet contains no characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// assert!(Alphabet::from_str("").is_empty());
+    /// assert!(!Alphabet::from_str("abc").is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns the character with a given rank, the inverse of [`Alphabet::index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - The rank of the character, as stored in a `Str`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(char)` if a character has rank `rank`, `None` otherwise. The sentinel
+    /// zero is not a character, so `char_at(0)` is always `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::from_str("abc");
+    /// assert_eq!(alphabet.char_at(2), Some('b'));
+    /// assert_eq!(alphabet.char_at(alphabet.index('c').unwrap()), Some('c'));
+    /// assert_eq!(alphabet.char_at(0), None);
+    /// assert_eq!(alphabet.char_at(4), None);
+    /// ```
+    pub fn char_at(&self, rank: usize) -> Option<char> {
+        rank.checked_sub(1).and_then(|i| self.chars.get(i).copied())
+    }
+
     /// Returns the size of characters needed to represent a given string over this alphabet.
     ///
     /// # Returns
@@ -218,7 +470,7 @@ impl Alphabet {
     /// let result = alphabet.char_size().unwrap();
     /// assert_eq!(result, CharSize::U8);
     /// ```
-    pub fn char_size(&self) -> Result<CharSize, Box<dyn std::error::Error>> {
+    pub fn char_size(&self) -> Result<CharSize, Error> {
         CharSize::from_alphabet_size(self.len())
     }
 
@@ -230,8 +482,12 @@ impl Alphabet {
     ///
     /// # Returns
     ///
-    /// `Ok(Char)` if the character is in the alphabet and the conversion to `Char` is successful,
-    /// `Err(Box<dyn std::error::Error>)` otherwise.
+    /// `Ok(Char)` if the character is in the alphabet and the alphabet fits in `Char`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`, and
+    /// [`Error::CharacterNotInAlphabet`] with position zero if `c` is not in the alphabet.
     ///
     /// # Examples
     ///
@@ -242,20 +498,42 @@ impl Alphabet {
     /// let result: u8 = alphabet.map_char('b').unwrap();
     /// assert_eq!(result, 2);
     /// ```
-    pub fn map_char<Char>(&self, c: char) -> Result<Char, Box<dyn std::error::Error>>
+    pub fn map_char<Char>(&self, c: char) -> Result<Char, Error>
     where
         Char: CharacterTrait,
     {
+        self.check_fits::<Char>()?;
+        self.map_char_at(c, 0)
+    }
+
+    /// Returns the table of byte indices, building it if this is the first time we need it.
+    fn byte_indices(&self) -> &[usize] {
+        self.byte_indices.get_or_init(|| {
+            (0..=u8::MAX)
+                .map(|b| self.index(char::from(b)).unwrap_or(0))
+                .collect()
+        })
+    }
+
+    /// Checks that the alphabet, and thus all its indices, fit in `Char`.
+    fn check_fits<Char: CharacterTrait>(&self) -> Result<(), Error> {
         if self.len() > Char::MAX {
-            return Err("Alphabet too large for Char type".into());
+            return Err(Error::AlphabetTooLarge {
+                size: self.len(),
+                max: Char::MAX,
+            });
         }
+        Ok(())
+    }
 
-        let idx = match self.index(c) {
-            None => return Err("Character not in alphabet".into()),
-            Some(idx) => idx,
-        };
-
-        Char::try_from(idx).map_err(|_| "Index conversion failed".into())
+    /// Maps the character `c` at index `position` in a string, for the error report.
+    /// The caller must check that the alphabet fits in `Char`.
+    fn map_char_at<Char: CharacterTrait>(&self, c: char, position: usize) -> Result<Char, Error> {
+        match self.index(c) {
+            None => Err(Error::CharacterNotInAlphabet { ch: c, position }),
+            // The alphabet fits in Char, so the conversion cannot fail.
+            Some(idx) => Ok(Char::try_from(idx).unwrap()),
+        }
     }
 
     /// Maps a Rust built-in string slice (str) to a vector of characters of another type (Char).
@@ -271,7 +549,12 @@ impl Alphabet {
     ///
     /// # Returns
     ///
-    /// `Ok(Vec<Char>)` if the conversion is successful, `Err(Box<dyn std::error::Error>)` otherwise.
+    /// `Ok(Vec<Char>)` if the conversion is successful.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`, and
+    /// [`Error::CharacterNotInAlphabet`] for the first character that is not in the alphabet.
     ///
     /// # Examples
     ///
@@ -282,17 +565,171 @@ impl Alphabet {
     /// let result: Vec<u8> = alphabet.map_str("abc").unwrap();
     /// assert_eq!(result, vec![1, 2, 3]);
     /// ```
-    pub fn map_str<Char>(&self, s: &str) -> Result<Vec<Char>, Box<dyn std::error::Error>>
+    pub fn map_str<Char>(&self, s: &str) -> Result<Vec<Char>, Error>
+    where
+        Char: CharacterTrait,
+    {
+        self.check_fits::<Char>()?;
+        s.chars()
+            .enumerate()
+            .map(|(i, c)| self.map_char_at(c, i))
+            .collect()
+    }
+
+    /// Maps a slice of bytes to a vector of characters of type `Char`.
+    ///
+    /// Each byte is read as the character with the same code point, as in
+    /// [`Alphabet::from_bytes`]. The bytes are looked up in a table rather than
+    /// hashed, and are not decoded as UTF-8, so this also works for binary data.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to map.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<Char>)` if the conversion is successful.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`, and
+    /// [`Error::CharacterNotInAlphabet`] for the first byte that is not in the alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::from_bytes(b"abc");
+    /// let result: Vec<u8> = alphabet.map_bytes(b"cab").unwrap();
+    /// assert_eq!(result, vec![3, 1, 2]);
+    /// assert!(alphabet.map_bytes::<u8>(b"abd").is_err());
+    /// ```
+    pub fn map_bytes<Char>(&self, bytes: &[u8]) -> Result<Vec<Char>, Error>
+    where
+        Char: CharacterTrait,
+    {
+        self.check_fits::<Char>()?;
+        let byte_indices = self.byte_indices();
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| match byte_indices[b as usize] {
+                0 => Err(Error::CharacterNotInAlphabet {
+                    ch: char::from(b),
+                    position: i,
+                }),
+                // The alphabet fits in Char, so the conversion cannot fail.
+                idx => Ok(Char::try_from(idx).unwrap()),
+            })
+            .collect()
+    }
+
+    /// Maps a slice of bytes to a vector of characters of type `Char`, mapping bytes
+    /// that are not in the alphabet to the sentinel zero.
+    ///
+    /// This is the byte version of [`Alphabet::map_str_lossy`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to map.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<Char>)` if the alphabet fits in `Char`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::from_bytes(b"abc");
+    /// let result: Vec<u8> = alphabet.map_bytes_lossy(b"ab\xffc").unwrap();
+    /// assert_eq!(result, vec![1, 2, 0, 3]);
+    /// ```
+    pub fn map_bytes_lossy<Char>(&self, bytes: &[u8]) -> Result<Vec<Char>, Error>
+    where
+        Char: CharacterTrait,
+    {
+        self.check_fits::<Char>()?;
+        let byte_indices = self.byte_indices();
+        // The alphabet fits in Char, so the conversions cannot fail.
+        Ok(bytes
+            .iter()
+            .map(|&b| Char::try_from(byte_indices[b as usize]).unwrap())
+            .collect())
+    }
+
+    /// Maps a Rust built-in string slice (str) to a vector of characters of another type (Char),
+    /// mapping characters that are not in the alphabet to the sentinel zero.
+    ///
+    /// This is useful when searching a text for a pattern whose alphabet we built from the
+    /// pattern alone. Characters in the text that do not occur in the pattern can never be part
+    /// of a match, and since no character in the alphabet maps to zero, mapping them to the
+    /// sentinel keeps them from matching anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The string slice to map.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<Char>)` if the alphabet fits in `Char`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::Alphabet;
+    ///
+    /// let alphabet = Alphabet::from_str("abc");
+    /// let result: Vec<u8> = alphabet.map_str_lossy("abxc").unwrap();
+    /// assert_eq!(result, vec![1, 2, 0, 3]);
+    /// ```
+    pub fn map_str_lossy<Char>(&self, s: &str) -> Result<Vec<Char>, Error>
     where
         Char: CharacterTrait,
     {
-        s.chars().map(|c| self.map_char(c)).collect()
+        self.check_fits::<Char>()?;
+        // The alphabet fits in Char, so the conversions cannot fail.
+        Ok(s.chars()
+            .map(|c| Char::try_from(self.index(c).unwrap_or(0)).unwrap())
+            .collect())
+    }
+}
+
+impl PartialEq for Alphabet {
+    /// Two alphabets are equal if they give the characters the same ranks, so
+    /// strings over one can be used with the other. The lookup tables are derived
+    /// from the ranks and are not compared, so a case-insensitive alphabet equals
+    /// the alphabet it was made from.
+    fn eq(&self, other: &Self) -> bool {
+        self.chars == other.chars
+    }
+}
+
+impl Eq for Alphabet {}
+
+/// Returns the character in `chars` if there is exactly one.
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::utils::Str;
+    use std::sync::Arc;
 
     #[test]
     fn test_alphabet_new() {
@@ -376,4 +813,141 @@ mod test {
         let result: Vec<u8> = alphabet.map_str("abc").unwrap();
         assert_eq!(result, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn test_alphabet_map_str_error() {
+        let alphabet = Alphabet::from_str("abc");
+        let result = alphabet.map_str::<u8>("abxc");
+        assert_eq!(
+            result,
+            Err(Error::CharacterNotInAlphabet {
+                ch: 'x',
+                position: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_alphabet_map_str_too_large() {
+        let letters: Vec<char> = (0..=u8::MAX).map(|c| c as char).collect();
+        let alphabet = Alphabet::new(&letters);
+        let result = alphabet.map_str::<u8>("abc");
+        assert_eq!(
+            result,
+            Err(Error::AlphabetTooLarge {
+                size: 256,
+                max: 254
+            })
+        );
+    }
+
+    #[test]
+    fn test_alphabet_from_bytes() {
+        let alphabet = Alphabet::from_bytes(b"hello\x00\xe5");
+        assert_eq!(alphabet.len(), 6);
+        assert_eq!(alphabet.char_at(1), Some('\0'));
+        assert_eq!(alphabet.char_at(6), Some('å'));
+        assert_eq!(Alphabet::from_bytes(b"\xe5"), Alphabet::from_str("å"));
+    }
+
+    #[test]
+    fn test_alphabet_map_bytes() {
+        let alphabet = Alphabet::bytes();
+        let bytes: Vec<u8> = (0..=u8::MAX).collect();
+        let x: Vec<u16> = alphabet.map_bytes(&bytes).unwrap();
+        assert_eq!(x, (1..=256).collect::<Vec<u16>>());
+        assert_eq!(
+            alphabet.map_bytes::<u8>(&bytes),
+            Err(Error::AlphabetTooLarge {
+                size: 256,
+                max: 254
+            })
+        );
+
+        let alphabet = Alphabet::from_str("ab");
+        assert_eq!(
+            alphabet.map_bytes::<u8>(b"abc"),
+            Err(Error::CharacterNotInAlphabet {
+                ch: 'c',
+                position: 2
+            })
+        );
+        assert_eq!(
+            alphabet.map_bytes_lossy::<u8>(b"b\xffa").unwrap(),
+            vec![2, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_predefined_alphabets() {
+        assert_eq!(
+            Alphabet::dna().map_str::<u8>("ACGT").unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(Alphabet::iupac().index('-'), Some(1));
+        assert_eq!(Alphabet::iupac().index('Y'), Some(17));
+        assert_eq!(Alphabet::protein().index('Y'), Some(20));
+        assert_eq!(Alphabet::protein_extended().index('Z'), Some(25));
+        assert!(Alphabet::ascii().contains('\0'));
+        assert!(Alphabet::printable().contains('~'));
+        assert!(!Alphabet::printable().contains('\x7f'));
+
+        // The ranks are fixed, so strings mapped over alphabets created at
+        // different times can be compared.
+        assert_eq!(Alphabet::protein(), Alphabet::protein());
+    }
+
+    #[test]
+    fn test_alphabet_ignore_case() {
+        let alphabet = Alphabet::iupac().ignore_case();
+        assert_eq!(alphabet.len(), 17);
+        assert_eq!(
+            alphabet.map_str::<u8>("acgtn-").unwrap(),
+            alphabet.map_str::<u8>("ACGTN-").unwrap()
+        );
+        assert_eq!(
+            alphabet.map_bytes::<u8>(b"acgtn-").unwrap(),
+            alphabet.map_str::<u8>("ACGTN-").unwrap()
+        );
+        assert_eq!(
+            alphabet.map_str::<u8>("ACGTx"),
+            Err(Error::CharacterNotInAlphabet {
+                ch: 'x',
+                position: 4
+            })
+        );
+
+        // Letters that are in the alphabet in both cases keep their own ranks.
+        let alphabet = Alphabet::from_str("Aab").ignore_case();
+        assert_eq!(alphabet.index('A'), Some(1));
+        assert_eq!(alphabet.index('a'), Some(2));
+        assert_eq!(alphabet.index('B'), Some(3));
+
+        // The byte table is rebuilt if it was built before ignoring case
+        let alphabet = Alphabet::dna();
+        assert!(alphabet.map_bytes::<u8>(b"a").is_err());
+        let alphabet = alphabet.ignore_case();
+        assert_eq!(alphabet.map_bytes::<u8>(b"a").unwrap(), vec![1]);
+
+        // ASCII already contains both cases.
+        let alphabet = Alphabet::ascii().ignore_case();
+        assert_ne!(alphabet.index('a'), alphabet.index('A'));
+
+        // Ignoring case does not change the ranks, so strings over the two
+        // alphabets are equal.
+        let dna = Arc::new(Alphabet::dna());
+        let dna_ignore_case = Arc::new(Alphabet::dna().ignore_case());
+        assert_eq!(dna, dna_ignore_case);
+        assert_eq!(
+            Str::<u8>::from_str("ACGT", &dna).unwrap(),
+            Str::<u8>::from_str("acgt", &dna_ignore_case).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_alphabet_map_str_lossy() {
+        let alphabet = Alphabet::from_str("abc");
+        let result: Vec<u8> = alphabet.map_str_lossy("xaby").unwrap();
+        assert_eq!(result, vec![0, 1, 2, 0]);
+    }
 }
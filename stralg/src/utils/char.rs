@@ -1,25 +1,39 @@
+use crate::Error;
+
 /// A trait for character types.
 ///
 /// This trait is used to define the character types that can be used in the library.
 pub trait CharacterTrait:
     Eq
+    + Ord
     + std::hash::Hash
     + TryFrom<usize, Error: std::fmt::Debug>
     + Copy
-    + Into<usize>
     + std::fmt::Debug
+    + Send
+    + Sync
     + 'static
 {
     const MAX: usize;
-    fn to_usize(&self) -> usize {
-        (*self).into()
-    }
+    fn to_usize(&self) -> usize;
 }
 impl CharacterTrait for u8 {
     const MAX: usize = u8::MAX as usize - 1; // -1 to leave room for the sentinel
+    fn to_usize(&self) -> usize {
+        *self as usize
+    }
 }
 impl CharacterTrait for u16 {
     const MAX: usize = u16::MAX as usize - 1; // -1 to leave room for the sentinel
+    fn to_usize(&self) -> usize {
+        *self as usize
+    }
+}
+impl CharacterTrait for u32 {
+    const MAX: usize = u32::MAX as usize - 1; // -1 to leave room for the sentinel
+    fn to_usize(&self) -> usize {
+        *self as usize
+    }
 }
 
 /// The size of characters needed to represent a given string.
@@ -29,6 +43,8 @@ pub enum CharSize {
     U8,
     /// 16 bits needed for each character
     U16,
+    /// 32 bits needed for each character
+    U32,
 }
 
 impl CharSize {
@@ -44,7 +60,8 @@ impl CharSize {
     ///
     /// # Errors
     ///
-    /// Returns an error if the alphabet is too large for known character types.
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet is too large for known
+    /// character types.
     ///
     /// # Examples
     ///
@@ -55,14 +72,17 @@ impl CharSize {
     /// let result = alphabet.char_size().unwrap();
     /// assert_eq!(result, CharSize::U8);
     /// ```
-    pub fn from_alphabet_size(size: usize) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_alphabet_size(size: usize) -> Result<Self, Error> {
         const U8_MAX: usize = <u8 as CharacterTrait>::MAX;
         const U16_MIN: usize = U8_MAX + 1;
         const U16_MAX: usize = <u16 as CharacterTrait>::MAX;
+        const U32_MIN: usize = U16_MAX + 1;
+        const U32_MAX: usize = <u32 as CharacterTrait>::MAX;
         match size {
             0..=U8_MAX => Ok(CharSize::U8),
-            U16_MIN..U16_MAX => Ok(CharSize::U16),
-            _ => Err("Alphabet too large for known Char types".into()),
+            U16_MIN..=U16_MAX => Ok(CharSize::U16),
+            U32_MIN..=U32_MAX => Ok(CharSize::U32),
+            _ => Err(Error::AlphabetTooLarge { size, max: U32_MAX }),
         }
     }
 }
@@ -84,15 +104,27 @@ mod tests {
         assert_eq!(result, CharSize::U8);
     }
 
+    #[test]
+    fn test_char_size_just_in_u16() {
+        let result = CharSize::from_alphabet_size((u16::MAX - 1) as usize).unwrap();
+        assert_eq!(result, CharSize::U16);
+    }
+
+    #[test]
+    fn test_char_size_just_barely_u32() {
+        let result = CharSize::from_alphabet_size(u16::MAX as usize).unwrap();
+        assert_eq!(result, CharSize::U32);
+    }
+
     #[test]
     fn test_just_too_large_alphabet() {
-        let result = CharSize::from_alphabet_size((u16::MAX) as usize);
+        let result = CharSize::from_alphabet_size((u32::MAX) as usize);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_way_too_large_alphabet() {
-        let result = CharSize::from_alphabet_size((u32::MAX) as usize);
+        let result = CharSize::from_alphabet_size(usize::MAX);
         assert!(result.is_err());
     }
 
@@ -1,7 +1,11 @@
 pub mod alphabet;
 pub mod char;
 pub mod str;
+pub mod str_slice;
+pub(crate) mod table;
 
 pub use alphabet::Alphabet;
 pub use char::{CharSize, CharacterTrait};
-pub use str::{Str, StrMapper, StrMappers};
+pub use str::{Chars, Str, StrMapper, StrMappers};
+pub use str_slice::{AsStrSlice, StrSlice};
+pub(crate) use table::CharTable;
@@ -1,15 +1,18 @@
 use super::{Alphabet, CharSize, CharacterTrait};
-use std::rc::Rc;
+use crate::Error;
+use std::sync::Arc;
 
 /// A string mapper that uses a custom alphabet for character encoding.
 ///
-/// This enum is used to select the correct mapper, and the underlying character type (u8 or u16).
+/// This enum is used to select the correct mapper, and the underlying character type (u8, u16 or u32).
 ///
 pub enum StrMappers {
     /// Mapping to u8 characters
     U8Mapper(StrMapper<u8>),
     /// Mapping to u16 characters
     U16Mapper(StrMapper<u16>),
+    /// Mapping to u32 characters
+    U32Mapper(StrMapper<u32>),
 }
 
 impl StrMappers {
@@ -29,21 +32,23 @@ impl StrMappers {
     ///
     /// ```
     /// use stralg::utils::{Alphabet, StrMappers};
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let alphabet = Rc::new(Alphabet::from_str("abc"));
+    /// let alphabet = Arc::new(Alphabet::from_str("abc"));
     /// let mapper = StrMappers::new(&alphabet);
     /// match mapper {
     ///    StrMappers::U8Mapper(_) => (),
     ///   _ => panic!("Expected StrMapper::U8"),
     /// }
     /// ```
-    pub fn new(alphabet: &Rc<Alphabet>) -> Self {
+    pub fn new(alphabet: &Arc<Alphabet>) -> Self {
         use CharSize::*;
         use StrMappers::*;
+        // An alphabet of chars always fits in u32, so this cannot fail.
         match alphabet.char_size().unwrap() {
             U8 => U8Mapper(StrMapper::new(alphabet)),
             U16 => U16Mapper(StrMapper::new(alphabet)),
+            U32 => U32Mapper(StrMapper::new(alphabet)),
         }
     }
 
@@ -63,11 +68,11 @@ impl StrMappers {
     ///
     /// ```
     /// use stralg::utils::StrMappers;
-    /// let mapper = StrMappers::new_from_str("abc").unwrap();
+    /// let mapper = StrMappers::new_from_str("abc");
     /// ```
-    pub fn new_from_str(s: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let alphabet = Rc::new(Alphabet::from_str(s));
-        Ok(Self::new(&alphabet))
+    pub fn new_from_str(s: &str) -> Self {
+        let alphabet = Arc::new(Alphabet::from_str(s));
+        Self::new(&alphabet)
     }
 
     /// Creates a new `StrMappers` instance from an array of string slices.
@@ -86,11 +91,35 @@ impl StrMappers {
     ///
     /// ```
     /// use stralg::utils::StrMappers;
-    /// let mapper = StrMappers::new_from_strs(&["abc", "def"]).unwrap();
+    /// let mapper = StrMappers::new_from_strs(&["abc", "def"]);
     /// ```
-    pub fn new_from_strs(strings: &[&str]) -> Result<Self, Box<dyn std::error::Error>> {
-        let alphabet = Rc::new(Alphabet::from_strs(strings));
-        Ok(Self::new(&alphabet))
+    pub fn new_from_strs(strings: &[&str]) -> Self {
+        let alphabet = Arc::new(Alphabet::from_strs(strings));
+        Self::new(&alphabet)
+    }
+
+    /// Creates a new `StrMappers` instance from a slice of bytes.
+    ///
+    /// This is a convenience method that creates an alphabet from the bytes, see
+    /// [`Alphabet::from_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - A slice of bytes to convert.
+    ///
+    /// # Returns
+    ///
+    /// A new `StrMappers` instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::StrMappers;
+    /// let mapper = StrMappers::new_from_bytes(b"\x00\xff");
+    /// ```
+    pub fn new_from_bytes(bytes: &[u8]) -> Self {
+        let alphabet = Arc::new(Alphabet::from_bytes(bytes));
+        Self::new(&alphabet)
     }
 }
 
@@ -100,7 +129,7 @@ where
     Char: CharacterTrait,
 {
     /// The alphabet used for character encoding.
-    pub alphabet: Rc<Alphabet>,
+    pub alphabet: Arc<Alphabet>,
     _phantom: std::marker::PhantomData<Char>,
 }
 
@@ -117,7 +146,7 @@ where
     /// # Returns
     ///
     /// A new `StrMapper` instance.
-    pub(self) fn new(alphabet: &Rc<Alphabet>) -> Self {
+    pub(self) fn new(alphabet: &Arc<Alphabet>) -> Self {
         Self {
             alphabet: alphabet.clone(),
             _phantom: std::marker::PhantomData,
@@ -136,18 +165,70 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if the string contains characters not in the alphabet.
-    pub fn map_str(&self, s: &str) -> Result<Str<Char>, Box<dyn std::error::Error>> {
+    /// Returns [`Error::CharacterNotInAlphabet`] for the first character in `s` that
+    /// is not in the alphabet.
+    pub fn map_str(&self, s: &str) -> Result<Str<Char>, Error> {
         let char_vector = self.alphabet.map_str::<Char>(s)?;
         Ok(Str::new(char_vector, &self.alphabet))
     }
+
+    /// Maps a string to a vector of characters using the alphabet, mapping characters
+    /// that are not in the alphabet to the sentinel.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - A string slice to convert.
+    ///
+    /// # Returns
+    ///
+    /// A vector of characters.
+    pub fn map_str_lossy(&self, s: &str) -> Str<Char> {
+        // The mapper was created for an alphabet that fits in Char, so this cannot fail.
+        let char_vector = self.alphabet.map_str_lossy::<Char>(s).unwrap();
+        Str::new(char_vector, &self.alphabet)
+    }
+
+    /// Maps a slice of bytes to a vector of characters using the alphabet.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to convert.
+    ///
+    /// # Returns
+    ///
+    /// A vector of characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CharacterNotInAlphabet`] for the first byte that is not in
+    /// the alphabet.
+    pub fn map_bytes(&self, bytes: &[u8]) -> Result<Str<Char>, Error> {
+        let char_vector = self.alphabet.map_bytes::<Char>(bytes)?;
+        Ok(Str::new(char_vector, &self.alphabet))
+    }
+
+    /// Maps a slice of bytes to a vector of characters using the alphabet, mapping
+    /// bytes that are not in the alphabet to the sentinel.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to convert.
+    ///
+    /// # Returns
+    ///
+    /// A vector of characters.
+    pub fn map_bytes_lossy(&self, bytes: &[u8]) -> Str<Char> {
+        // The mapper was created for an alphabet that fits in Char, so this cannot fail.
+        let char_vector = self.alphabet.map_bytes_lossy::<Char>(bytes).unwrap();
+        Str::new(char_vector, &self.alphabet)
+    }
 }
 
 /// A string type that uses a custom alphabet for character encoding.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(PartialEq, Clone)]
 pub struct Str<Char: CharacterTrait> {
     char_vector: Vec<Char>,
-    pub alphabet: Rc<Alphabet>,
+    pub alphabet: Arc<Alphabet>,
 }
 
 impl<Char: CharacterTrait> Str<Char> {
@@ -166,16 +247,16 @@ impl<Char: CharacterTrait> Str<Char> {
     ///
     /// ```
     /// use stralg::utils::{Alphabet, Str};
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let alphabet = Rc::new(Alphabet::new(&['a', 'b', 'c']));
+    /// let alphabet = Arc::new(Alphabet::new(&['a', 'b', 'c']));
     /// let chars = vec![1u8, 2, 3];
     /// let s = Str::new(chars, &alphabet);
     /// assert_eq!(s[0], 1);
     /// assert_eq!(s[1], 2);
     /// assert_eq!(s[2], 3);
     /// ```
-    pub fn new(x: Vec<Char>, alphabet: &Rc<Alphabet>) -> Self {
+    pub fn new(x: Vec<Char>, alphabet: &Arc<Alphabet>) -> Self {
         Self {
             char_vector: x,
             alphabet: alphabet.clone(),
@@ -193,29 +274,61 @@ impl<Char: CharacterTrait> Str<Char> {
     ///
     /// A new `Str` instance.
     ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`, and
+    /// [`Error::CharacterNotInAlphabet`] for the first character in `s` that is not
+    /// in the alphabet.
+    ///
     /// # Examples
     ///
     /// ```
     /// use stralg::utils::{Alphabet, Str};
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let alphabet = Rc::new(Alphabet::new(&['a', 'b', 'c']));
+    /// let alphabet = Arc::new(Alphabet::new(&['a', 'b', 'c']));
     /// let s = Str::<u8>::from_str("abc", &alphabet).unwrap();
     /// assert_eq!(s[0], 1);
     /// assert_eq!(s[1], 2);
     /// assert_eq!(s[2], 3);
     /// ```
-    pub fn from_str(s: &str, alphabet: &Rc<Alphabet>) -> Result<Self, Box<dyn std::error::Error>> {
-        if alphabet.len() > Char::MAX {
-            return Err("Alphabet too large for Char type".into());
-        }
-
-        let x = s
-            .chars()
-            .map(|c| alphabet.map_char(c))
-            .collect::<Result<Vec<Char>, Box<dyn std::error::Error>>>()?;
+    pub fn from_str(s: &str, alphabet: &Arc<Alphabet>) -> Result<Self, Error> {
+        let x = alphabet.map_str(s)?;
+        Ok(Self::new(x, alphabet))
+    }
 
-        Ok(Self::new(x, &alphabet))
+    /// Creates a new `Str` from a slice of bytes and a given alphabet.
+    ///
+    /// The bytes are read as characters with the same code points, see
+    /// [`Alphabet::from_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to convert.
+    /// * `alphabet` - A reference-counted pointer to the alphabet.
+    ///
+    /// # Returns
+    ///
+    /// A new `Str` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlphabetTooLarge`] if the alphabet does not fit in `Char`, and
+    /// [`Error::CharacterNotInAlphabet`] for the first byte that is not in the alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::{Alphabet, Str};
+    /// use std::sync::Arc;
+    ///
+    /// let alphabet = Arc::new(Alphabet::bytes());
+    /// let s = Str::<u16>::from_bytes(b"\x00\x01\xff", &alphabet).unwrap();
+    /// assert_eq!(s[..], [1, 2, 256]);
+    /// ```
+    pub fn from_bytes(bytes: &[u8], alphabet: &Arc<Alphabet>) -> Result<Self, Error> {
+        let x = alphabet.map_bytes(bytes)?;
+        Ok(Self::new(x, alphabet))
     }
 
     /// Creates a new `Str` from a string slice using the same alphabet
@@ -234,9 +347,9 @@ impl<Char: CharacterTrait> Str<Char> {
     ///
     /// ```
     /// use stralg::utils::{Alphabet, Str};
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let alphabet = Rc::new(Alphabet::new(&['a', 'b', 'c']));
+    /// let alphabet = Arc::new(Alphabet::new(&['a', 'b', 'c']));
     /// let chars = vec![1u8, 2, 3];
     /// let s1 = Str::new(chars, &alphabet);
     /// let s2 = s1.translate_to_this_alphabet("abc").unwrap();
@@ -244,7 +357,7 @@ impl<Char: CharacterTrait> Str<Char> {
     /// assert_eq!(s2[1], 2);
     /// assert_eq!(s2[2], 3);
     /// ```
-    pub fn translate_to_this_alphabet(&self, s: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn translate_to_this_alphabet(&self, s: &str) -> Result<Self, Error> {
         let alphabet = self.alphabet.clone();
         Self::from_str(s, &alphabet)
     }
@@ -259,9 +372,9 @@ impl<Char: CharacterTrait> Str<Char> {
     ///
     /// ```
     /// use stralg::utils::{Alphabet, Str};
-    /// use std::rc::Rc;
+    /// use std::sync::Arc;
     ///
-    /// let alphabet = Rc::new(Alphabet::new(&['a', 'b', 'c']));
+    /// let alphabet = Arc::new(Alphabet::new(&['a', 'b', 'c']));
     /// let chars = vec![1u8, 2, 3];
     /// let s = Str::new(chars, &alphabet);
     /// assert_eq!(s.len(), 3);
@@ -270,9 +383,104 @@ impl<Char: CharacterTrait> Str<Char> {
         self.char_vector.len()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Char> {
+    /// Returns `true` if the string has no characters.
+    pub fn is_empty(&self) -> bool {
+        self.char_vector.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Char> {
         self.char_vector.iter()
     }
+
+    /// Returns an iterator over the characters of the string, decoded through the
+    /// alphabet.
+    ///
+    /// Characters that are not in the alphabet, such as the sentinel, are decoded
+    /// as U+FFFD.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::{Alphabet, Str};
+    /// use std::sync::Arc;
+    ///
+    /// let alphabet = Arc::new(Alphabet::from_str("abc"));
+    /// let s: Str<u8> = Str::from_str("cab", &alphabet).unwrap();
+    /// assert_eq!(s.chars().collect::<Vec<char>>(), vec!['c', 'a', 'b']);
+    /// assert_eq!(s.chars().rev().collect::<String>(), "bac");
+    /// ```
+    pub fn chars(&self) -> Chars<'_, Char> {
+        Chars::new(&self.char_vector, &self.alphabet)
+    }
+}
+
+/// An iterator over the characters of a `Str` or `StrSlice`, decoded back to `char`.
+///
+/// Created by [`Str::chars`] and [`StrSlice::chars`](super::StrSlice::chars).
+#[derive(Clone)]
+pub struct Chars<'a, Char: CharacterTrait> {
+    iter: std::slice::Iter<'a, Char>,
+    alphabet: &'a Alphabet,
+}
+
+impl<'a, Char: CharacterTrait> Chars<'a, Char> {
+    pub(crate) fn new(chars: &'a [Char], alphabet: &'a Alphabet) -> Self {
+        Chars {
+            iter: chars.iter(),
+            alphabet,
+        }
+    }
+
+    fn decode(&self, a: &Char) -> char {
+        self.alphabet
+            .char_at(a.to_usize())
+            .unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+}
+
+impl<Char: CharacterTrait> Iterator for Chars<'_, Char> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let a = self.iter.next()?;
+        Some(self.decode(a))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<Char: CharacterTrait> DoubleEndedIterator for Chars<'_, Char> {
+    fn next_back(&mut self) -> Option<char> {
+        let a = self.iter.next_back()?;
+        Some(self.decode(a))
+    }
+}
+
+impl<Char: CharacterTrait> ExactSizeIterator for Chars<'_, Char> {}
+
+impl<Char: CharacterTrait> std::fmt::Display for Str<Char> {
+    /// Writes the string decoded through the alphabet, so `to_string()` gives back
+    /// the text the string was created from. Characters that are not in the
+    /// alphabet, such as the sentinel, are written as U+FFFD.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl<Char: CharacterTrait> std::fmt::Debug for Str<Char> {
+    /// Shows the decoded string rather than the raw ranks and the alphabet.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Str").field(&self.to_string()).finish()
+    }
+}
+
+impl<Char: CharacterTrait> AsRef<[Char]> for Str<Char> {
+    fn as_ref(&self) -> &[Char] {
+        &self.char_vector
+    }
 }
 
 impl<Char: CharacterTrait> std::ops::Index<usize> for Str<Char>
@@ -357,9 +565,39 @@ impl<Char: CharacterTrait> std::ops::IndexMut<std::ops::RangeFull> for Str<Char>
 mod test {
     use super::*;
 
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<Alphabet>();
+        assert_send_sync::<Str<u8>>();
+        assert_send_sync::<Str<u32>>();
+        assert_send_sync::<StrMapper<u16>>();
+        assert_send_sync::<StrMappers>();
+    }
+
+    #[test]
+    fn test_decode() {
+        let alphabet = Arc::new(Alphabet::from_str("blåbærsyltetøy"));
+        let x: Str<u8> = Str::from_str("blåbærsyltetøy", &alphabet).unwrap();
+        assert_eq!(x.to_string(), "blåbærsyltetøy");
+        assert_eq!(x.chars().len(), 14);
+        assert_eq!(x.chars().nth(2), Some('å'));
+        assert_eq!(format!("{:?}", x), "Str(\"blåbærsyltetøy\")");
+        assert_eq!(format!("{:?}", x.slice(2..5)), "StrSlice(\"åbæ\")");
+    }
+
+    #[test]
+    fn test_decode_sentinel() {
+        let alphabet = Arc::new(Alphabet::from_str("ab"));
+        let x: Str<u16> = Str::new(vec![2, 1, 0], &alphabet);
+        assert_eq!(x.to_string(), "ba\u{FFFD}");
+        assert_eq!(Str::<u16>::new(vec![], &alphabet).to_string(), "");
+    }
+
     #[test]
     fn test_str_mapper_new() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let mapper = StrMappers::new(&alphabet);
         match mapper {
             StrMappers::U8Mapper(_) => (),
@@ -370,7 +608,7 @@ mod test {
     #[test]
     fn test_str_mapper_large_alphabet() {
         let letters: Vec<char> = (0..=u8::MAX).map(|c| c as char).collect(); // Too many chars for u8 (with sentinel)
-        let alphabet = Rc::new(Alphabet::new(&letters));
+        let alphabet = Arc::new(Alphabet::new(&letters));
         let mapper = StrMappers::new(&alphabet);
         match mapper {
             StrMappers::U16Mapper(_) => (),
@@ -378,28 +616,31 @@ mod test {
         }
     }
 
-    /* Skipped for now because we unwrap in StrMappers::new instead of returning a Result
     #[test]
-    fn test_way_too_large_alphabet() {
-        let letters: Vec<char> = (0..=u16::MAX)
-            .map(|c| std::char::from_u32(c).unwrap())
-            .collect(); // Too many chars for u16
-        let alphabet = Rc::new(Alphabet::new(&letters));
+    fn test_str_mapper_u32_alphabet() {
+        let letters: Vec<char> = (0..0x12000).filter_map(std::char::from_u32).collect(); // Too many chars for u16 (with sentinel)
+        let alphabet = Arc::new(Alphabet::new(&letters));
         let mapper = StrMappers::new(&alphabet);
-        assert!(mapper.is_err());
+        match mapper {
+            StrMappers::U32Mapper(mapper) => {
+                let s = mapper.map_str(&letters.iter().collect::<String>()).unwrap();
+                assert_eq!(s.len(), letters.len());
+                assert_eq!(s[s.len() - 1] as usize, letters.len());
+            }
+            _ => panic!("Expected StrMapper::U32"),
+        }
     }
-    */
 
     #[test]
     fn test_sized_str_mapper_new() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let mapper = StrMapper::<u8>::new(&alphabet);
         assert_eq!(mapper.alphabet.len(), 3);
     }
 
     #[test]
     fn test_sized_str_mapper_map_str() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let mapper = StrMapper::<u8>::new(&alphabet);
         let result = mapper.map_str("abc").unwrap();
         assert_eq!(result, Str::new(vec![1, 2, 3], &alphabet));
@@ -407,7 +648,7 @@ mod test {
 
     #[test]
     fn test_sized_str_mapper_map_str_error() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let mapper = StrMapper::<u8>::new(&alphabet);
         let result = mapper.map_str("def");
         assert!(result.is_err());
@@ -415,15 +656,23 @@ mod test {
 
     #[test]
     fn test_sized_str_mapper_map_str_error_2() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let mapper = StrMapper::<u8>::new(&alphabet);
         let result = mapper.map_str("abcd");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_sized_str_mapper_map_str_lossy() {
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
+        let mapper = StrMapper::<u8>::new(&alphabet);
+        let result = mapper.map_str_lossy("abcd");
+        assert_eq!(result, Str::new(vec![1, 2, 3, 0], &alphabet));
+    }
+
     #[test]
     fn test_str_mappers_new_from_str() {
-        let mapper = StrMappers::new_from_str("abc").unwrap();
+        let mapper = StrMappers::new_from_str("abc");
         match mapper {
             StrMappers::U8Mapper(_) => (),
             _ => panic!("Expected StrMapper::U8"),
@@ -432,7 +681,7 @@ mod test {
 
     #[test]
     fn test_str_mappers_new_from_strs() {
-        let mapper = StrMappers::new_from_strs(&["abc", "def"]).unwrap();
+        let mapper = StrMappers::new_from_strs(&["abc", "def"]);
         match mapper {
             StrMappers::U8Mapper(_) => (),
             _ => panic!("Expected StrMapper::U8"),
@@ -441,7 +690,7 @@ mod test {
 
     #[test]
     fn test_mapper_map_str() {
-        let mapper = StrMappers::new_from_str("abc").unwrap();
+        let mapper = StrMappers::new_from_str("abc");
         let mapper = match mapper {
             StrMappers::U8Mapper(mapper) => mapper,
             _ => panic!("Expected StrMapper::U8"),
@@ -452,7 +701,7 @@ mod test {
 
     #[test]
     fn test_str_new() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let chars = vec![1u8, 2, 3];
         let s = Str::new(chars, &alphabet);
         assert_eq!(s[0], 1);
@@ -462,7 +711,7 @@ mod test {
 
     #[test]
     fn test_str_from_str() {
-        let alphabet = Rc::new(Alphabet::from_str("abc"));
+        let alphabet = Arc::new(Alphabet::from_str("abc"));
         let s = Str::<u8>::from_str("abc", &alphabet).unwrap();
         assert_eq!(s[0], 1);
         assert_eq!(s[1], 2);
@@ -472,7 +721,7 @@ mod test {
     #[test]
     fn test_from_str_alphabet_too_large() {
         let letters: Vec<char> = (0..=u8::MAX).map(|c| c as char).collect(); // Too many chars for u8 (with sentinel)
-        let alphabet = Rc::new(Alphabet::new(&letters));
+        let alphabet = Arc::new(Alphabet::new(&letters));
         let result = Str::<u8>::from_str("abc", &alphabet);
         assert!(result.is_err());
     }
@@ -0,0 +1,336 @@
+use super::str::Chars;
+use super::{Alphabet, CharacterTrait, Str};
+use std::ops::{Bound, Index, Range, RangeBounds, RangeFrom, RangeFull, RangeTo};
+use std::sync::Arc;
+
+/// A borrowed view into a `Str`, or part of one, that keeps the alphabet.
+///
+/// Indexing a `Str` with a range gives a plain slice of characters, which is enough
+/// for comparing characters but loses the alphabet. A `StrSlice` keeps a reference
+/// to the alphabet, so it can be passed to the algorithms that take strings, and
+/// be turned back into a `String`.
+///
+/// # Examples
+///
+/// ```
+/// use stralg::utils::{Alphabet, Str};
+/// use stralg::border_array;
+/// use std::sync::Arc;
+///
+/// let alphabet = Arc::new(Alphabet::from_str("ab"));
+/// let x: Str<u8> = Str::from_str("babaab", &alphabet).unwrap();
+/// let s = x.slice(1..5);
+/// assert_eq!(s.len(), 4);
+/// assert_eq!(s.to_string(), "abaa");
+/// assert_eq!(border_array(&s), vec![0, 0, 1, 1]);
+/// assert_eq!(s.slice(1..3).to_string(), "ba");
+/// ```
+#[derive(Clone, Copy)]
+pub struct StrSlice<'a, Char: CharacterTrait> {
+    chars: &'a [Char],
+    pub alphabet: &'a Arc<Alphabet>,
+}
+
+/// Converts a range to a start and end index for a sequence of length `n`.
+fn bounds(range: impl RangeBounds<usize>, n: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i + 1,
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => n,
+    };
+    start..end
+}
+
+impl<'a, Char: CharacterTrait> StrSlice<'a, Char> {
+    /// Creates a new `StrSlice` from a slice of characters and the alphabet they are over.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - The characters in the slice.
+    /// * `alphabet` - A reference-counted pointer to the alphabet.
+    ///
+    /// # Returns
+    ///
+    /// A new `StrSlice` instance.
+    pub fn new(chars: &'a [Char], alphabet: &'a Arc<Alphabet>) -> Self {
+        StrSlice { chars, alphabet }
+    }
+
+    /// Returns the length of the slice.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Returns `true` if the slice has no characters.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns an iterator over the characters in the slice.
+    pub fn iter(&self) -> std::slice::Iter<'a, Char> {
+        self.chars.iter()
+    }
+
+    /// Returns an iterator over the characters in the slice, decoded through the
+    /// alphabet. Characters that are not in the alphabet are decoded as U+FFFD.
+    pub fn chars(&self) -> Chars<'a, Char> {
+        Chars::new(self.chars, self.alphabet)
+    }
+
+    /// Returns the characters in the slice.
+    pub fn as_slice(&self) -> &'a [Char] {
+        self.chars
+    }
+
+    /// Returns a sub-slice of the slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the sub-slice, relative to this slice.
+    ///
+    /// # Returns
+    ///
+    /// A `StrSlice` over the same alphabet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StrSlice<'a, Char> {
+        let range = bounds(range, self.len());
+        StrSlice::new(&self.chars[range], self.alphabet)
+    }
+
+    /// Copies the slice into a new `Str`.
+    pub fn to_str(&self) -> Str<Char> {
+        Str::new(self.chars.to_vec(), self.alphabet)
+    }
+}
+
+impl<Char: CharacterTrait> Str<Char> {
+    /// Returns a borrowed view of part of the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the slice.
+    ///
+    /// # Returns
+    ///
+    /// A `StrSlice` over the string's alphabet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stralg::utils::{Alphabet, Str};
+    /// use std::sync::Arc;
+    ///
+    /// let alphabet = Arc::new(Alphabet::from_str("abc"));
+    /// let x: Str<u8> = Str::from_str("abcabc", &alphabet).unwrap();
+    /// assert_eq!(x.slice(..3), x.slice(3..));
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StrSlice<'_, Char> {
+        let range = bounds(range, self.len());
+        StrSlice::new(&self[range], &self.alphabet)
+    }
+}
+
+/// Strings that can be viewed as a `StrSlice`, so algorithms can take both
+/// `Str` and `StrSlice` arguments.
+pub trait AsStrSlice<Char: CharacterTrait> {
+    /// Returns a view of the whole string.
+    fn as_str_slice(&self) -> StrSlice<'_, Char>;
+}
+
+impl<Char: CharacterTrait> AsStrSlice<Char> for Str<Char> {
+    fn as_str_slice(&self) -> StrSlice<'_, Char> {
+        StrSlice::new(&self[..], &self.alphabet)
+    }
+}
+
+impl<Char: CharacterTrait> AsStrSlice<Char> for StrSlice<'_, Char> {
+    fn as_str_slice(&self) -> StrSlice<'_, Char> {
+        *self
+    }
+}
+
+impl<'a, Char: CharacterTrait> From<&'a Str<Char>> for StrSlice<'a, Char> {
+    fn from(x: &'a Str<Char>) -> Self {
+        x.as_str_slice()
+    }
+}
+
+impl<Char: CharacterTrait> From<StrSlice<'_, Char>> for Str<Char> {
+    fn from(x: StrSlice<'_, Char>) -> Self {
+        x.to_str()
+    }
+}
+
+impl<Char: CharacterTrait> PartialEq for StrSlice<'_, Char> {
+    fn eq(&self, other: &Self) -> bool {
+        // Slices of the same string share the alphabet, so we only compare the
+        // alphabets themselves if they are different objects.
+        self.chars == other.chars
+            && (Arc::ptr_eq(self.alphabet, other.alphabet) || self.alphabet == other.alphabet)
+    }
+}
+
+impl<Char: CharacterTrait> Eq for StrSlice<'_, Char> {}
+
+impl<Char: CharacterTrait> PartialEq<Str<Char>> for StrSlice<'_, Char> {
+    fn eq(&self, other: &Str<Char>) -> bool {
+        *self == other.as_str_slice()
+    }
+}
+
+impl<Char: CharacterTrait> PartialEq<StrSlice<'_, Char>> for Str<Char> {
+    fn eq(&self, other: &StrSlice<'_, Char>) -> bool {
+        self.as_str_slice() == *other
+    }
+}
+
+impl<Char: CharacterTrait> std::fmt::Display for StrSlice<'_, Char> {
+    /// Writes the characters of the slice, decoded through the alphabet. Characters
+    /// that are not in the alphabet, such as the sentinel, are written as U+FFFD.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl<Char: CharacterTrait> std::fmt::Debug for StrSlice<'_, Char> {
+    /// Shows the decoded slice rather than the raw ranks and the alphabet.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StrSlice").field(&self.to_string()).finish()
+    }
+}
+
+impl<'a, Char: CharacterTrait> IntoIterator for StrSlice<'a, Char> {
+    type Item = &'a Char;
+    type IntoIter = std::slice::Iter<'a, Char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars.iter()
+    }
+}
+
+impl<Char: CharacterTrait> Index<usize> for StrSlice<'_, Char> {
+    type Output = Char;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.chars[index]
+    }
+}
+
+impl<Char: CharacterTrait> Index<Range<usize>> for StrSlice<'_, Char> {
+    type Output = [Char];
+
+    fn index(&self, range: Range<usize>) -> &Self::Output {
+        &self.chars[range]
+    }
+}
+
+impl<Char: CharacterTrait> Index<RangeFrom<usize>> for StrSlice<'_, Char> {
+    type Output = [Char];
+
+    fn index(&self, range: RangeFrom<usize>) -> &Self::Output {
+        &self.chars[range]
+    }
+}
+
+impl<Char: CharacterTrait> Index<RangeTo<usize>> for StrSlice<'_, Char> {
+    type Output = [Char];
+
+    fn index(&self, range: RangeTo<usize>) -> &Self::Output {
+        &self.chars[range]
+    }
+}
+
+impl<Char: CharacterTrait> Index<RangeFull> for StrSlice<'_, Char> {
+    type Output = [Char];
+
+    fn index(&self, _range: RangeFull) -> &Self::Output {
+        self.chars
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn str_from(s: &str) -> Str<u8> {
+        let alphabet = Arc::new(Alphabet::from_str("abcd"));
+        Str::from_str(s, &alphabet).unwrap()
+    }
+
+    #[test]
+    fn test_slice() {
+        let x = str_from("abcdabcd");
+        let s = x.slice(2..6);
+        assert_eq!(s.len(), 4);
+        assert_eq!(s[0], 3);
+        assert_eq!(&s[1..3], &[4, 1]);
+        assert_eq!(s.iter().copied().collect::<Vec<u8>>(), vec![3, 4, 1, 2]);
+        assert_eq!(s.to_string(), "cdab");
+    }
+
+    #[test]
+    fn test_sub_slices() {
+        let x = str_from("abcdabcd");
+        let s = x.slice(1..=6);
+        assert_eq!(s.slice(..), s);
+        assert_eq!(s.slice(1..).to_string(), "cdabc");
+        assert_eq!(s.slice(..2).to_string(), "bc");
+        assert_eq!(s.slice(3..3).to_string(), "");
+        assert!(s.slice(3..3).is_empty());
+    }
+
+    #[test]
+    fn test_equality() {
+        let x = str_from("abcdabcd");
+        assert_eq!(x.slice(..4), x.slice(4..));
+        assert_ne!(x.slice(..4), x.slice(1..5));
+        assert_eq!(x.slice(..4), str_from("abcd"));
+        assert_eq!(str_from("abcd"), x.slice(4..));
+        assert_eq!(x.slice(4..).to_str(), str_from("abcd"));
+
+        // Equal characters over different alphabets are different strings
+        let other = Arc::new(Alphabet::from_str("abcde"));
+        let y: Str<u8> = Str::from_str("abcd", &other).unwrap();
+        assert_ne!(x.slice(..4), y.slice(..));
+    }
+
+    #[test]
+    fn test_unknown_characters() {
+        let alphabet = Arc::new(Alphabet::from_str("ab"));
+        let x: Str<u8> = Str::new(vec![1, 0, 2], &alphabet);
+        assert_eq!(x.slice(..).to_string(), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_algorithms_accept_slices() {
+        let x = str_from("dabcabcaabd");
+        let s = x.slice(1..10);
+        let owned = s.to_str();
+        assert_eq!(crate::z_array(&s), crate::z_array(&owned));
+        assert_eq!(crate::border_array(&s), crate::border_array(&owned));
+        assert_eq!(
+            crate::suffix_array::sais(&s),
+            crate::suffix_array::sais(&owned)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_bounds() {
+        let x = str_from("abcd");
+        x.slice(2..5);
+    }
+}
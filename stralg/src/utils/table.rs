@@ -0,0 +1,128 @@
+/// Alphabets up to this size, including the sentinel, get dense tables.
+const DENSE_SIGMA: usize = 256;
+
+/// A table with a row of values for each character, as used for the transitions of
+/// automata, the children of tree nodes and the bit masks of bit-parallel searches.
+///
+/// For small alphabets each row holds a value for every character, so a lookup is a
+/// single index. For large alphabets that would take σ space per row, so we only
+/// store the values that differ from the row's default, sorted by character, and
+/// look them up with a binary search.
+pub(crate) struct CharTable<T> {
+    /// The size of the alphabet, so all characters are less than `sigma`
+    sigma: usize,
+    /// The rows, dense or sparse
+    rows: Rows<T>,
+}
+
+enum Rows<T> {
+    /// The row `r` is at `values[r * sigma..(r + 1) * sigma]`
+    Dense(Vec<T>),
+    /// The default of each row and the characters that have other values
+    Sparse(Vec<(T, Vec<(usize, T)>)>),
+}
+
+impl<T: Copy + PartialEq> CharTable<T> {
+    /// Creates a table without rows for characters less than `sigma`.
+    pub(crate) fn new(sigma: usize) -> CharTable<T> {
+        let rows = if sigma <= DENSE_SIGMA {
+            Rows::Dense(Vec::new())
+        } else {
+            Rows::Sparse(Vec::new())
+        };
+        CharTable { sigma, rows }
+    }
+
+    /// Adds a row where all characters have the value `default`, and returns its index.
+    pub(crate) fn add_row(&mut self, default: T) -> usize {
+        match &mut self.rows {
+            Rows::Dense(values) => {
+                values.extend(std::iter::repeat_n(default, self.sigma));
+                values.len() / self.sigma - 1
+            }
+            Rows::Sparse(rows) => {
+                rows.push((default, Vec::new()));
+                rows.len() - 1
+            }
+        }
+    }
+
+    /// Returns the value of character `a` in row `r`.
+    pub(crate) fn get(&self, r: usize, a: usize) -> T {
+        match &self.rows {
+            Rows::Dense(values) => values[r * self.sigma + a],
+            Rows::Sparse(rows) => {
+                let (default, values) = &rows[r];
+                match values.binary_search_by_key(&a, |&(b, _)| b) {
+                    Ok(i) => values[i].1,
+                    Err(_) => *default,
+                }
+            }
+        }
+    }
+
+    /// Sets the value of character `a` in row `r`.
+    pub(crate) fn set(&mut self, r: usize, a: usize, value: T) {
+        match &mut self.rows {
+            Rows::Dense(values) => values[r * self.sigma + a] = value,
+            Rows::Sparse(rows) => {
+                let values = &mut rows[r].1;
+                match values.binary_search_by_key(&a, |&(b, _)| b) {
+                    Ok(i) => values[i].1 = value,
+                    Err(i) => values.insert(i, (a, value)),
+                }
+            }
+        }
+    }
+
+    /// Returns an iterator over the characters in row `r` whose values differ from
+    /// `default`, and their values, in increasing order of the characters.
+    pub(crate) fn entries(&self, r: usize, default: T) -> impl Iterator<Item = (usize, T)> + '_ {
+        let (dense, sparse) = match &self.rows {
+            Rows::Dense(values) => (&values[r * self.sigma..(r + 1) * self.sigma], &[][..]),
+            Rows::Sparse(rows) => (&[][..], &rows[r].1[..]),
+        };
+        dense
+            .iter()
+            .copied()
+            .enumerate()
+            .chain(sparse.iter().copied())
+            .filter(move |&(_, value)| value != default)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_table(sigma: usize) {
+        let mut table = CharTable::new(sigma);
+        assert_eq!(table.add_row(0), 0);
+        assert_eq!(table.add_row(7), 1);
+        let a = sigma - 1;
+        table.set(0, a, 1);
+        table.set(0, 2, 2);
+        table.set(1, 2, 3);
+        table.set(0, 2, 4);
+
+        assert_eq!(table.get(0, a), 1);
+        assert_eq!(table.get(0, 2), 4);
+        assert_eq!(table.get(0, 1), 0);
+        assert_eq!(table.get(1, 2), 3);
+        assert_eq!(table.get(1, a), 7);
+        let entries: Vec<(usize, usize)> = table.entries(0, 0).collect();
+        assert_eq!(entries, vec![(2, 4), (a, 1)]);
+        let entries: Vec<(usize, usize)> = table.entries(1, 7).collect();
+        assert_eq!(entries, vec![(2, 3)]);
+    }
+
+    #[test]
+    fn test_dense_table() {
+        check_table(DENSE_SIGMA);
+    }
+
+    #[test]
+    fn test_sparse_table() {
+        check_table(DENSE_SIGMA + 1);
+    }
+}